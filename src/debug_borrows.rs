@@ -0,0 +1,271 @@
+use crate::NearSafeCell;
+#[cfg(feature = "debug-borrows")]
+use core::ops::Range;
+use core::{
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+};
+#[cfg(feature = "debug-borrows")]
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, MutexGuard, PoisonError,
+    },
+    vec::Vec,
+};
+
+/// The number of tracked exclusive borrows live across all cells, letting checks skip the lock while there are none.
+#[cfg(feature = "debug-borrows")]
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+/// The address range of every [`NearSafeCell`] with a live tracked exclusive borrow.
+///
+/// Accesses are checked for intersecting any of them rather than for being equal, so that e.g. an element of a borrowed array or a nested cell counts as aliasing it.
+/// Kept out of line so the feature leaves the layout of [`NearSafeCell`] untouched.
+/// A cell can neither move nor be dropped while it is borrowed, so an entry never outlives the cell it belongs to.
+#[cfg(feature = "debug-borrows")]
+static BORROWED: Mutex<Vec<Range<usize>>> = Mutex::new(Vec::new());
+
+#[cfg(feature = "debug-borrows")]
+fn borrowed() -> MutexGuard<'static, Vec<Range<usize>>> {
+    BORROWED.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Returns `true` if the address ranges `a` and `b` share at least one byte.
+#[cfg(feature = "debug-borrows")]
+fn overlaps(a: &Range<usize>, b: &Range<usize>) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+/// Deregisters a tracked exclusive borrow when dropped.
+pub(crate) struct ExclusiveBorrow {
+    /// The address range of the borrowed cell in [`BORROWED`], or [`None`] if it is zero-sized and thus untracked.
+    #[cfg(feature = "debug-borrows")]
+    key: Option<Range<usize>>,
+}
+
+#[cfg(feature = "debug-borrows")]
+impl Drop for ExclusiveBorrow {
+    fn drop(&mut self) {
+        if let Some(key) = &self.key {
+            let mut borrowed = borrowed();
+            if let Some(i) = borrowed.iter().position(|other| other == key) {
+                borrowed.swap_remove(i);
+            }
+            LIVE.fetch_sub(1, Ordering::Release);
+        }
+    }
+}
+
+impl<T: ?Sized> NearSafeCell<T> {
+    /// Returns a [`TrackedMut`] to the wrapped `T`, bypassing the borrow checker.
+    /// Behaves like [`Self::get_mut_unsafe`], except that with the `debug-borrows` feature the borrow stays registered until the [`TrackedMut`] is dropped,
+    /// making any other checked access to this [`NearSafeCell`] in the meantime panic.
+    /// # Safety
+    /// There exists no other `&T` or `&mut T` to the wrapped `T` currently and until the returned [`TrackedMut`] is dropped.
+    pub unsafe fn get_mut_tracked(&self) -> TrackedMut<'_, T> {
+        let borrow = self.borrow_exclusive();
+        self.assert_single_thread();
+        TrackedMut {
+            value: &mut *self.get_mut_ptr(),
+            _borrow: borrow,
+        }
+    }
+    /// Returns the address range of this [`NearSafeCell`] as kept in [`BORROWED`], or [`None`] if it is zero-sized,
+    /// as distinct zero-sized cells may share an address and cannot alias anything anyway.
+    #[cfg(feature = "debug-borrows")]
+    fn borrow_key(&self) -> Option<Range<usize>> {
+        let size = core::mem::size_of_val(self);
+        let addr = core::ptr::from_ref(self).cast::<u8>().addr();
+        (size != 0).then(|| addr..addr + size)
+    }
+    /// Panics if a tracked exclusive borrow overlapping this [`NearSafeCell`] is currently live.
+    #[cfg(feature = "debug-borrows")]
+    pub(crate) fn check_borrows(&self) {
+        if LIVE.load(Ordering::Acquire) == 0 {
+            return;
+        }
+        if let Some(key) = self.borrow_key() {
+            let aliased = borrowed().iter().any(|other| overlaps(other, &key));
+            assert!(
+                !aliased,
+                "NearSafeCell accessed while it is mutably borrowed"
+            );
+        }
+    }
+    #[cfg(not(feature = "debug-borrows"))]
+    #[allow(clippy::unused_self)]
+    #[inline(always)]
+    pub(crate) fn check_borrows(&self) {}
+    /// Registers a tracked exclusive borrow of this [`NearSafeCell`] until the returned [`ExclusiveBorrow`] is dropped,
+    /// panicking if another one overlapping it is currently live.
+    #[cfg(feature = "debug-borrows")]
+    pub(crate) fn borrow_exclusive(&self) -> ExclusiveBorrow {
+        let key = self.borrow_key();
+        if let Some(key) = &key {
+            let mut borrowed = borrowed();
+            let aliased = borrowed.iter().any(|other| overlaps(other, key));
+            if !aliased {
+                borrowed.push(key.clone());
+                LIVE.fetch_add(1, Ordering::Release);
+            }
+            drop(borrowed);
+            assert!(
+                !aliased,
+                "NearSafeCell mutably borrowed while it is already mutably borrowed"
+            );
+        }
+        ExclusiveBorrow { key }
+    }
+    #[cfg(not(feature = "debug-borrows"))]
+    #[allow(clippy::unused_self)]
+    #[inline]
+    pub(crate) fn borrow_exclusive(&self) -> ExclusiveBorrow {
+        ExclusiveBorrow {}
+    }
+}
+
+/// A `&mut T` to the value wrapped in a [`NearSafeCell`], returned by [`NearSafeCell::get_mut_tracked`].
+///
+/// With the `debug-borrows` feature the [`NearSafeCell`] counts as mutably borrowed until this is dropped, otherwise it is just the `&mut T`.
+pub struct TrackedMut<'a, T: ?Sized> {
+    value: &'a mut T,
+    _borrow: ExclusiveBorrow,
+}
+
+impl<T: ?Sized> Deref for TrackedMut<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        self.value
+    }
+}
+impl<T: ?Sized> DerefMut for TrackedMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.value
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for TrackedMut<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("TrackedMut").field(&self.value).finish()
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracked_mut() {
+        let cell = NearSafeCell::new(1);
+        {
+            let mut value = unsafe { cell.get_mut_tracked() };
+            *value += 1;
+        }
+        assert_eq!(cell.get(), &2);
+        unsafe { *cell.get_mut_tracked() += 1 };
+        assert_eq!(cell.get(), &3);
+    }
+
+    #[cfg(feature = "debug-borrows")]
+    #[test]
+    #[should_panic(expected = "NearSafeCell accessed while it is mutably borrowed")]
+    fn tracked_mut_then_get() {
+        let cell = NearSafeCell::new(1);
+        let _value = unsafe { cell.get_mut_tracked() };
+        cell.get();
+    }
+
+    #[cfg(feature = "debug-borrows")]
+    #[test]
+    #[should_panic(expected = "NearSafeCell accessed while it is mutably borrowed")]
+    fn tracked_mut_then_get_mut_unsafe() {
+        let cell = NearSafeCell::new(1);
+        let _value = unsafe { cell.get_mut_tracked() };
+        unsafe { cell.get_mut_unsafe() };
+    }
+
+    #[test]
+    fn tracked_mut_zero_sized() {
+        // Both cells live at the same address, which must not count as aliasing.
+        let cells = [NearSafeCell::new(()), NearSafeCell::new(())];
+        let _value = unsafe { cells[0].get_mut_tracked() };
+        cells[1].get();
+    }
+
+    #[cfg(feature = "debug-borrows")]
+    #[test]
+    fn tracked_mut_released() {
+        let cell = NearSafeCell::new(1);
+        drop(unsafe { cell.get_mut_tracked() });
+        assert_eq!(cell.get(), &1);
+        let _value = unsafe { cell.get_mut_tracked() };
+    }
+
+    #[cfg(feature = "debug-borrows")]
+    #[test]
+    #[should_panic(expected = "NearSafeCell mutably borrowed while it is already mutably borrowed")]
+    fn tracked_mut_twice() {
+        let cell = NearSafeCell::new(1);
+        let _value = unsafe { cell.get_mut_tracked() };
+        let _other = unsafe { cell.get_mut_tracked() };
+    }
+
+    #[cfg(feature = "debug-borrows")]
+    #[test]
+    #[should_panic(expected = "NearSafeCell accessed while it is mutably borrowed")]
+    fn with_mut_then_aliasing() {
+        let cell = NearSafeCell::new([1, 2]);
+        unsafe { cell.with_mut_then(|_| *cell.index_mut_unchecked(0) = 3, |_| ()) };
+    }
+
+    #[cfg(all(feature = "debug-borrows", feature = "alloc"))]
+    #[test]
+    #[should_panic(expected = "NearSafeCell accessed while it is mutably borrowed")]
+    fn tracked_mut_then_two_vecs_mut() {
+        let cell = NearSafeCell::new((alloc::vec![1u8], alloc::vec![2u8]));
+        let _value = unsafe { cell.get_mut_tracked() };
+        unsafe {
+            cell.two_vecs_mut(
+                |c| core::ptr::addr_of_mut!((*c).0),
+                |c| core::ptr::addr_of_mut!((*c).1),
+            )
+        };
+    }
+
+    #[cfg(feature = "debug-borrows")]
+    #[test]
+    #[should_panic(expected = "NearSafeCell accessed while it is mutably borrowed")]
+    fn with_mut_unsafe_aliasing() {
+        let cell = NearSafeCell::new(1);
+        unsafe { cell.with_mut_unsafe(|_| cell.get()) };
+    }
+
+    #[cfg(all(feature = "debug-borrows", not(near_safe_cell_assert_single_thread)))]
+    #[test]
+    #[should_panic(expected = "NearSafeCell accessed while it is mutably borrowed")]
+    fn tracked_mut_then_get_element() {
+        let array = NearSafeCell::new([1, 2, 3]);
+        let _value = unsafe { array.get_mut_tracked() };
+        array.as_array_of_cells()[1].get();
+    }
+
+    #[cfg(all(feature = "debug-borrows", not(near_safe_cell_assert_single_thread)))]
+    #[test]
+    #[should_panic(expected = "NearSafeCell mutably borrowed while it is already mutably borrowed")]
+    fn tracked_mut_element_then_whole() {
+        let array = NearSafeCell::new([1, 2, 3]);
+        let _element = unsafe { array.as_array_of_cells()[1].get_mut_tracked() };
+        let _value = unsafe { array.get_mut_tracked() };
+    }
+
+    #[cfg(all(feature = "debug-borrows", not(near_safe_cell_assert_single_thread)))]
+    #[test]
+    fn tracked_mut_disjoint_elements() {
+        let array = NearSafeCell::new([1, 2, 3]);
+        let cells = array.as_array_of_cells();
+        let _first = unsafe { cells[0].get_mut_tracked() };
+        let _second = unsafe { cells[1].get_mut_tracked() };
+        assert_eq!(cells[2].get(), &3);
+    }
+}
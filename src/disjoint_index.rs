@@ -0,0 +1,80 @@
+use core::{
+    ops::{Range, RangeInclusive},
+    slice::{GetDisjointMutError, SliceIndex},
+};
+
+/// An index into a slice `[U]` that [`NearSafeCell::get_disjoint_mut_unchecked`](crate::NearSafeCell::get_disjoint_mut_unchecked)
+/// and [`NearSafeCell::index_disjoint_mut_unchecked`](crate::NearSafeCell::index_disjoint_mut_unchecked) accept,
+/// mirroring the index types of [`slice::get_disjoint_mut`].
+///
+/// Implemented for [`usize`], [`Range<usize>`] and [`RangeInclusive<usize>`] only, and sealed against further implementations.
+pub trait DisjointIndex<U>: SliceIndex<[U]> + Clone + sealed::Sealed<U> {}
+
+mod sealed {
+    use super::{GetDisjointMutError, SliceIndex};
+
+    /// Forwards to the slice methods, whose index trait cannot be named on stable.
+    pub trait Sealed<U>: SliceIndex<[U]> + Sized {
+        fn get_disjoint_mut<const N: usize>(
+            slice: &mut [U],
+            indices: [Self; N],
+        ) -> Result<[&mut Self::Output; N], GetDisjointMutError>;
+        /// # Safety
+        /// The `indices` are in bounds and pairwise disjoint.
+        unsafe fn get_disjoint_unchecked_mut<const N: usize>(
+            slice: &mut [U],
+            indices: [Self; N],
+        ) -> [&mut Self::Output; N];
+    }
+}
+
+macro_rules! impl_disjoint_index {
+    ($($index:ty),*) => {$(
+        impl<U> DisjointIndex<U> for $index {}
+        impl<U> sealed::Sealed<U> for $index {
+            fn get_disjoint_mut<const N: usize>(
+                slice: &mut [U],
+                indices: [Self; N],
+            ) -> Result<[&mut Self::Output; N], GetDisjointMutError> {
+                slice.get_disjoint_mut(indices)
+            }
+            unsafe fn get_disjoint_unchecked_mut<const N: usize>(
+                slice: &mut [U],
+                indices: [Self; N],
+            ) -> [&mut Self::Output; N] {
+                slice.get_disjoint_unchecked_mut(indices)
+            }
+        }
+    )*};
+}
+
+impl_disjoint_index!(usize, Range<usize>, RangeInclusive<usize>);
+
+/// Returns the parts of `slice` indexed by `indices`, all derived from the one `slice` so that none invalidates another.
+/// # Panics
+/// Panics if any of `indices` is out of bounds, and with debug assertions or the `debug-borrows` feature enabled also if any two of them overlap,
+/// naming `caller` in the message.
+/// # Safety
+/// The `indices` are pairwise disjoint.
+pub(crate) unsafe fn disjoint_mut<'a, U, I: DisjointIndex<U>, const N: usize>(
+    slice: &'a mut [U],
+    indices: [I; N],
+    caller: &str,
+) -> [&'a mut I::Output; N] {
+    if cfg!(any(debug_assertions, feature = "debug-borrows")) {
+        return match I::get_disjoint_mut(slice, indices) {
+            Ok(parts) => parts,
+            Err(GetDisjointMutError::OverlappingIndices) => {
+                panic!("{} called with overlapping indices", caller)
+            }
+            Err(GetDisjointMutError::IndexOutOfBounds) => {
+                panic!("{} called with an out of bounds index", caller)
+            }
+        };
+    }
+    for idx in &indices {
+        // Only the bounds check, as out of bounds indices are UB for `get_disjoint_unchecked_mut`.
+        let _ = &slice[idx.clone()];
+    }
+    I::get_disjoint_unchecked_mut(slice, indices)
+}
@@ -0,0 +1,123 @@
+use crate::NearSafeCell;
+use core::{
+    fmt::Debug,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Two [`NearSafeCell`]s of which one is the readable front and the other the writable back,
+/// with [`DoubleBuffer::flip`] swapping their roles without moving either `T`.
+///
+/// Readers get the front through [`DoubleBuffer::front`] while a single writer prepares the next value
+/// through [`DoubleBuffer::back_mut`] and then publishes it with [`DoubleBuffer::flip`].
+pub struct DoubleBuffer<T> {
+    buffers: [NearSafeCell<T>; 2],
+    front: AtomicUsize,
+}
+
+impl<T: Default> Default for DoubleBuffer<T> {
+    fn default() -> Self {
+        Self::new(T::default(), T::default())
+    }
+}
+
+impl<T> DoubleBuffer<T> {
+    const_unless_loom! {
+        /// Constructs a new [`DoubleBuffer`] with `front` as the readable and `back` as the writable buffer.
+        pub fn new(front: T, back: T) -> Self {
+            Self {
+                buffers: [NearSafeCell::new(front), NearSafeCell::new(back)],
+                front: AtomicUsize::new(0),
+            }
+        }
+    }
+    /// Returns a `&T` to the current front buffer.
+    pub fn front(&self) -> &T {
+        self.buffers[self.front.load(Ordering::Acquire)].get()
+    }
+    /// Returns a `&mut T` to the current back buffer.
+    /// # Safety
+    /// There exists no other `&T` or `&mut T` to the current back buffer, including any `&T` returned by [`Self::front`] before the last [`Self::flip`],
+    /// and [`Self::flip`] is not called until the returned `&mut T` is dropped.
+    pub unsafe fn back_mut(&self) -> &mut T {
+        self.buffers[1 - self.front.load(Ordering::Acquire)].get_mut_unsafe()
+    }
+    /// Swaps the front and back buffers, publishing the previous back buffer to readers.
+    /// # Safety
+    /// No `&mut T` returned by [`Self::back_mut`] is live currently, as the back buffer it points to becomes the front buffer readable through [`Self::front`].
+    pub unsafe fn flip(&self) {
+        self.front.fetch_xor(1, Ordering::AcqRel);
+    }
+    /// Consumes this [`DoubleBuffer`], returning the wrapped `(front, back)`.
+    pub fn unwrap(self) -> (T, T) {
+        let front = self.front.into_inner();
+        let [a, b] = self.buffers;
+        if front == 0 {
+            (a.unwrap(), b.unwrap())
+        } else {
+            (b.unwrap(), a.unwrap())
+        }
+    }
+}
+
+impl<T: Debug> Debug for DoubleBuffer<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DoubleBuffer")
+            .field("front", self.front())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flipping() {
+        let buffer = DoubleBuffer::new(1, 2);
+        assert_eq!(buffer.front(), &1);
+        unsafe { buffer.flip() };
+        assert_eq!(buffer.front(), &2);
+        unsafe { *buffer.back_mut() = 3 };
+        assert_eq!(buffer.front(), &2);
+        unsafe { buffer.flip() };
+        assert_eq!(buffer.front(), &3);
+        assert_eq!(buffer.unwrap(), (3, 2));
+    }
+
+    #[test]
+    fn producer_consumer() {
+        let buffer = DoubleBuffer::<[u32; 4]>::default();
+        for frame in 1..=8 {
+            // Producer: render the next frame into the back buffer, then publish it.
+            unsafe { buffer.back_mut() }.fill(frame);
+            unsafe { buffer.flip() };
+            // Consumer: only ever observes complete frames.
+            assert_eq!(buffer.front(), &[frame; 4]);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn producer_consumer_threads() {
+        use std::sync::mpsc;
+        let buffer = &DoubleBuffer::<[u32; 4]>::default();
+        let (frames, received) = mpsc::sync_channel(0);
+        let (acks, acked) = mpsc::sync_channel(0);
+        std::thread::scope(|s| {
+            s.spawn(move || {
+                for frame in 1..=8 {
+                    // Safety: The consumer acknowledged the previous frame, so it no longer reads the buffer that became the back one.
+                    unsafe { buffer.back_mut() }.fill(frame);
+                    unsafe { buffer.flip() };
+                    frames.send(frame).unwrap();
+                    acked.recv().unwrap();
+                }
+            });
+            for frame in received {
+                assert_eq!(buffer.front(), &[frame; 4]);
+                acks.send(()).unwrap();
+            }
+        });
+        assert_eq!(buffer.front(), &[8; 4]);
+    }
+}
@@ -0,0 +1,45 @@
+use crate::NearSafeCell;
+
+/// A fixed `header` followed by a variable-length `tail`, laid out in declaration order.
+///
+/// Usually created sized, e.g. as a `HeaderSlice<H, [U; N]>`, and then unsized to a `HeaderSlice<H, [U]>` behind a pointer,
+/// such as a `Box<NearSafeCell<HeaderSlice<H, [U]>>>`.
+#[repr(C)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HeaderSlice<H, S: ?Sized> {
+    /// The fixed-size leading part.
+    pub header: H,
+    /// The trailing, possibly unsized part.
+    pub tail: S,
+}
+
+impl<H, U> NearSafeCell<HeaderSlice<H, [U]>> {
+    /// Returns a `&mut H` to the wrapped header and a `&mut [U]` to the wrapped tail, bypassing the borrow checker.
+    /// # Safety
+    /// There exists no other `&HeaderSlice<H, [U]>` or `&mut HeaderSlice<H, [U]>` to the wrapped value, nor to its header or tail,
+    /// currently and until the returned references are dropped.
+    pub unsafe fn header_and_tail_mut(&self) -> (&mut H, &mut [U]) {
+        let HeaderSlice { header, tail } = self.get_mut_unsafe();
+        (header, tail)
+    }
+}
+
+#[cfg(all(test, feature = "alloc", not(loom)))]
+mod tests {
+    use super::*;
+    use alloc::boxed::Box;
+
+    #[test]
+    fn header_and_tail_mut() {
+        let cell: Box<NearSafeCell<HeaderSlice<usize, [u8]>>> =
+            Box::new(NearSafeCell::new(HeaderSlice {
+                header: 0,
+                tail: [1, 2, 3],
+            }));
+        let (header, tail) = unsafe { cell.header_and_tail_mut() };
+        *header = tail.len();
+        tail.reverse();
+        assert_eq!(cell.get().header, 3);
+        assert_eq!(cell.get().tail, [3, 2, 1]);
+    }
+}
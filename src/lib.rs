@@ -1,4 +1,5 @@
 #![no_std]
+#![cfg_attr(feature = "unsize", feature(coerce_unsized))]
 #![deny(
     warnings,
     missing_docs,
@@ -7,22 +8,106 @@
     clippy::all,
     clippy::pedantic
 )]
+// Handing out `&mut T` from `&self` is the entire point of this crate.
+#![allow(clippy::mut_from_ref)]
 #![doc = include_str!("../Readme.md")]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(any(feature = "std", near_safe_cell_assert_single_thread))]
+extern crate std;
+
 use core::{
+    borrow::Borrow,
     cell::UnsafeCell,
     default::Default,
     fmt::{Binary, Debug, Display, LowerExp, LowerHex, Octal, Pointer, UpperExp, UpperHex},
-    ops::{Deref, DerefMut, IndexMut},
+    hash::{Hash, Hasher},
+    mem::MaybeUninit,
+    ops::{Deref, DerefMut, IndexMut, Range, RangeBounds, Shl, ShlAssign, Shr, ShrAssign},
+    pin::Pin,
+    ptr::NonNull,
+    slice::ChunksMut,
+    sync::atomic::{fence, Ordering},
 };
 
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use alloc::sync::Arc;
+/// Only emits the given items if [`NearSafeCell<T>`] is `#[repr(transparent)]` over `T`,
+/// which the `near_safe_cell_assert_single_thread` cfg breaks by giving each cell additional state, and the `loom` cfg by swapping its [`UnsafeCell`].
+macro_rules! if_transparent {
+    ($($item:item)*) => {$(
+        #[cfg(not(any(near_safe_cell_assert_single_thread, loom)))]
+        $item
+    )*};
+}
+
+/// Emits the given function as a `const fn`, unless the `loom` cfg makes it call into loom's non-const cell.
+macro_rules! const_unless_loom {
+    ($(#[$attr:meta])* $vis:vis fn $($rest:tt)*) => {
+        #[cfg(not(loom))]
+        $(#[$attr])* $vis const fn $($rest)*
+        #[cfg(loom)]
+        $(#[$attr])* $vis fn $($rest)*
+    };
+}
+
+#[cfg(near_safe_cell_tsan)]
+mod tsan {
+    // Provided by the thread sanitizer runtime when building with `-Zsanitizer=thread`.
+    extern "C" {
+        pub fn __tsan_acquire(addr: *mut core::ffi::c_void);
+        pub fn __tsan_release(addr: *mut core::ffi::c_void);
+    }
+}
+
+mod debug_borrows;
+pub use debug_borrows::TrackedMut;
+mod disjoint_index;
+use disjoint_index::disjoint_mut;
+pub use disjoint_index::DisjointIndex;
+#[cfg(target_has_atomic = "ptr")]
+mod double_buffer;
+#[cfg(target_has_atomic = "ptr")]
+pub use double_buffer::DoubleBuffer;
+mod header_slice;
+pub use header_slice::HeaderSlice;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+mod owned_borrow;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+pub use owned_borrow::OwnedBorrowGuard;
+mod value_cell;
+use value_cell::ValueCell;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::iter::FromIterator;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+#[cfg(near_safe_cell_assert_single_thread)]
+use std::sync::PoisonError;
+
 /// A more ergonomic [`UnsafeCell`] replacement.
 ///
-/// Note that unlike [`UnsafeCell<T>`], [`NearSafeCell<T>`] does implement [`Sync`]\(and [`RefUnwindSafe`](std::panic::RefUnwindSafe) if std is enabled) where `T: Sync`.
+/// Note that unlike [`UnsafeCell<T>`], [`NearSafeCell<T>`] does implement [`Sync`]\(and [`RefUnwindSafe`](core::panic::RefUnwindSafe) if std is enabled) where `T: Sync`.
 /// This is because the only way to break its safety is by either calling [`NearSafeCell::get_mut_unsafe`](NearSafeCell::get_mut_unsafe)
 /// or dereferencing the pointer from [`NearSafeCell::get_(mut_)ptr`](NearSafeCell::get_ptr),
 /// both of which are themselves unsafe and have identical safety requirements that, if upheld properly, still guarantee [`Sync`] correctness.
-pub struct NearSafeCell<T>(UnsafeCell<T>);
+///
+/// Unless the `loom` or `near_safe_cell_assert_single_thread` cfg is enabled, [`NearSafeCell<T>`] is guaranteed to be `#[repr(transparent)]` over `T`,
+/// i.e. it has the same size, alignment and ABI as `T`, and pointers to either can be cast to pointers to the other.
+///
+/// `T` may also be unsized, e.g. a `Box<NearSafeCell<[T]>>` or `Box<NearSafeCell<dyn FnMut()>>` coerced from a sized one.
+///
+/// Comparison, hashing and formatting delegate to the wrapped `T` through [`NearSafeCell::get`], observing its value at the time of the call.
+/// Mutating a cell while it is a key in e.g. a `BTreeMap` breaks the map's invariants, just like it would through a [`Cell`](core::cell::Cell).
+#[cfg_attr(not(any(near_safe_cell_assert_single_thread, loom)), repr(transparent))]
+pub struct NearSafeCell<T: ?Sized> {
+    #[cfg(near_safe_cell_assert_single_thread)]
+    owner: std::sync::Mutex<Option<std::thread::ThreadId>>,
+    value: ValueCell<T>,
+}
 
 impl<T: Default> Default for NearSafeCell<T> {
     fn default() -> Self {
@@ -30,21 +115,119 @@ impl<T: Default> Default for NearSafeCell<T> {
     }
 }
 
+impl<T: Clone> Clone for NearSafeCell<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.get().clone())
+    }
+}
+
 impl<T> NearSafeCell<T> {
-    /// Constructs a new [`NearSafeCell`] wrapping a `T`.
-    pub const fn new(val: T) -> Self {
-        Self(UnsafeCell::new(val))
+    const_unless_loom! {
+        /// Constructs a new [`NearSafeCell`] wrapping a `T`.
+        pub fn new(val: T) -> Self {
+            Self {
+                #[cfg(near_safe_cell_assert_single_thread)]
+                owner: std::sync::Mutex::new(None),
+                value: ValueCell::new(val),
+            }
+        }
+    }
+    const_unless_loom! {
+        /// Constructs a new [`NearSafeCell`] taking over the `T` wrapped in an [`UnsafeCell<T>`].
+        pub fn from_unsafe_cell(cell: UnsafeCell<T>) -> Self {
+            Self {
+                #[cfg(near_safe_cell_assert_single_thread)]
+                owner: std::sync::Mutex::new(None),
+                value: ValueCell::from_unsafe_cell(cell),
+            }
+        }
+    }
+    /// Constructs a new [`NearSafeCell`] wrapping the `T` produced by `f` if it succeeds.
+    /// # Errors
+    /// Returns the error of `f` if it fails.
+    pub fn try_new<E>(f: impl FnOnce() -> Result<T, E>) -> Result<Self, E> {
+        f().map(Self::new)
+    }
+    /// Replaces the wrapped `T` with `val`, returning this [`NearSafeCell`] for chaining.
+    #[must_use]
+    pub fn with_value(mut self, val: T) -> Self {
+        *self.get_mut() = val;
+        self
     }
     /// Consumes this [`NearSafeCell`], returning the wrapped `T`.
+    /// Owning the cell already rules out any other access to the `T`, so unlike the accessors taking `&self` there is nothing to check and no unchecked variant.
     pub fn unwrap(self) -> T {
-        self.0.into_inner()
+        self.value.into_inner()
+    }
+    /// Consumes this [`NearSafeCell`], returning the wrapped `T` as an [`UnsafeCell<T>`].
+    pub fn into_unsafe_cell(self) -> UnsafeCell<T> {
+        self.value.into_unsafe_cell()
+    }
+    /// Consumes this [`NearSafeCell`], returning a new one wrapping the result of `f` applied to the wrapped `T`.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> NearSafeCell<U> {
+        NearSafeCell::new(f(self.unwrap()))
+    }
+    /// Consumes this [`NearSafeCell`], returning a new one wrapping the result of `f` applied to the wrapped `T` if it succeeds.
+    /// # Errors
+    /// Returns the error of `f` if it fails, dropping the cell.
+    pub fn try_map<U, E>(self, f: impl FnOnce(T) -> Result<U, E>) -> Result<NearSafeCell<U>, E> {
+        f(self.unwrap()).map(NearSafeCell::new)
+    }
+    /// Replaces the wrapped `T` with `val`, returning the previous one, see [`core::cell::Cell::replace`].
+    /// Not named `replace` so as not to shadow methods of `T` reached through [`DerefMut`], e.g. [`Option::replace`].
+    pub fn replace_value(&mut self, val: T) -> T {
+        core::mem::replace(self.get_mut(), val)
+    }
+    /// Replaces the wrapped `T` with `val`, returning the previous one, through a shared reference.
+    /// # Safety
+    /// There exists no other `&T` or `&mut T` to the wrapped `T` currently.
+    pub unsafe fn replace_value_unchecked(&self, val: T) -> T {
+        core::mem::replace(self.get_mut_unsafe(), val)
+    }
+    /// Replaces the wrapped `T` with `T::default()`, returning the previous one, see [`core::cell::Cell::take`].
+    /// Not named `take` so as not to shadow methods of `T` reached through [`DerefMut`], e.g. [`Option::take`].
+    pub fn take_value(&mut self) -> T
+    where
+        T: Default,
+    {
+        self.replace_value(T::default())
     }
+    /// Swaps the wrapped `T`s of this and the `other` [`NearSafeCell`], see [`core::cell::Cell::swap`].
+    /// Not named `swap` so as not to shadow methods of `T` reached through [`DerefMut`], e.g. [`slice::swap`].
+    pub fn swap_with(&mut self, other: &mut NearSafeCell<T>) {
+        core::mem::swap(self.get_mut(), other.get_mut());
+    }
+}
+
+impl<T: ?Sized> NearSafeCell<T> {
     /// Returns a `&mut T` to the wrapped `T`, bypassing the borrow checker.
+    ///
+    /// With the `debug-borrows` feature this panics if a tracked borrow, e.g. from [`Self::get_mut_tracked`], is live,
+    /// but the returned `&mut T` is a plain reference and thus not tracked itself, see [`Self::with_mut_unsafe`] for one that is.
     /// # Safety
     /// There exists no other `&T` or `&mut T` to the wrapped `T` currently and until the returned `&mut T` is dropped.
     pub unsafe fn get_mut_unsafe(&self) -> &mut T {
+        self.check_borrows();
+        self.assert_single_thread();
         &mut *self.get_mut_ptr()
     }
+    /// Returns a [`Pin<&mut T>`](Pin) to the wrapped `T`, bypassing the borrow checker.
+    /// Allows e.g. polling a future stored in the cell through a shared reference.
+    /// # Safety
+    /// There exists no other `&T` or `&mut T` to the wrapped `T` currently and until the returned [`Pin<&mut T>`](Pin) is dropped,
+    /// and the wrapped `T` is never moved again until it is dropped, upholding the [`Pin`] contract.
+    pub unsafe fn get_pin_mut_unsafe(&self) -> Pin<&mut T> {
+        Pin::new_unchecked(self.get_mut_unsafe())
+    }
+    /// Returns a [`Pin<&mut T>`](Pin) to the wrapped `T` of this pinned [`NearSafeCell`], structurally pinning it.
+    /// Unlike [`Self::get_pin_mut_unsafe`] this does not have to promise the `T` is never moved, as the pinned cell itself already guarantees that.
+    /// # Safety
+    /// There exists no other `&T` or `&mut T` to the wrapped `T` currently and until the returned [`Pin<&mut T>`](Pin) is dropped,
+    /// and the wrapped `T` is treated as structurally pinned, i.e. no unpinned `&mut T` to it is ever created while the cell is pinned.
+    #[must_use]
+    pub unsafe fn get_pinned_mut_unsafe(self: Pin<&Self>) -> Pin<&mut T> {
+        Pin::new_unchecked(self.get_ref().get_mut_unsafe())
+    }
     /// Returns a `&mut T::Output` to a part of the wrapped `T` indexed by `Idx`.
     /// Helps avoiding having to even temporarily [`Self::get_mut_unsafe`] the whole T
     /// just to get a subset of it, making it easier and more obvious to uphold the aliasing rules.
@@ -54,24 +237,688 @@ impl<T> NearSafeCell<T> {
     where
         T: IndexMut<Idx>,
     {
-        (*self.get_mut_ptr()).index_mut(idx)
+        self.check_borrows();
+        self.assert_single_thread();
+        (&mut *self.get_mut_ptr()).index_mut(idx)
+    }
+    /// Returns a `&mut I::Output` to each of the parts of the slice wrapped in `T` indexed by `indices`, e.g. for mutating several elements at once,
+    /// see [`slice::get_disjoint_mut`].
+    /// # Panics
+    /// Panics if any of `indices` is out of bounds, and with debug assertions or the `debug-borrows` feature enabled also if any two of them overlap.
+    /// # Safety
+    /// The `indices` are pairwise disjoint,
+    /// and there exists no other `&T` or `&mut T` to the whole wrapped `T`, nor to any of the indexed parts, currently and until the returned references are dropped.
+    pub unsafe fn get_disjoint_mut_unchecked<'a, U: 'a, I: DisjointIndex<U>, const N: usize>(
+        &'a self,
+        indices: [I; N],
+    ) -> [&'a mut I::Output; N]
+    where
+        T: AsMut<[U]>,
+    {
+        disjoint_mut(
+            self.get_mut_unsafe().as_mut(),
+            indices,
+            "get_disjoint_mut_unchecked",
+        )
+    }
+    /// Returns a `&mut I::Output` to each of the two parts of the slice wrapped in `T` indexed by `a` and `b`, e.g. two non-overlapping ranges of it.
+    /// # Panics
+    /// Panics if `a` or `b` is out of bounds, and with debug assertions or the `debug-borrows` feature enabled also if the two overlap.
+    /// # Safety
+    /// The parts indexed by `a` and `b` are disjoint,
+    /// and there exists no other `&T` or `&mut T` to the whole wrapped `T`, nor to either of those parts, currently and until both returned references are dropped.
+    pub unsafe fn index_disjoint_mut_unchecked<'a, U: 'a, I: DisjointIndex<U>>(
+        &'a self,
+        a: I,
+        b: I,
+    ) -> (&'a mut I::Output, &'a mut I::Output)
+    where
+        T: AsMut<[U]>,
+    {
+        let [a, b] = disjoint_mut(
+            self.get_mut_unsafe().as_mut(),
+            [a, b],
+            "index_disjoint_mut_unchecked",
+        );
+        (a, b)
+    }
+    /// Runs `f` with a `&mut T` to the wrapped `T`, then `g` with a `&T` to it, returning both results.
+    /// The mutable borrow ends before the shared one begins, so the two never overlap.
+    /// # Safety
+    /// There exists no other `&T` or `&mut T` to the wrapped `T` while `f` runs.
+    pub unsafe fn with_mut_then<R1, R2>(
+        &self,
+        f: impl FnOnce(&mut T) -> R1,
+        g: impl FnOnce(&T) -> R2,
+    ) -> (R1, R2) {
+        let r1 = f(&mut self.get_mut_tracked());
+        let r2 = g(self.get());
+        (r1, r2)
+    }
+    /// Runs `f` with a `&mut T` to the wrapped `T`, returning its result.
+    /// Unlike with [`Self::get_mut_unsafe`], the `&mut T` cannot outlive `f`, confining the exclusive borrow to the closure body.
+    /// With the `debug-borrows` feature the borrow stays registered while `f` runs.
+    /// # Safety
+    /// There exists no other `&T` or `&mut T` to the wrapped `T` while `f` runs, including through nested calls on this [`NearSafeCell`] inside `f`.
+    pub unsafe fn with_mut_unsafe<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self.get_mut_tracked())
     }
     /// Returns a `&T` to the wrapped `T`.
     pub fn get(&self) -> &T {
+        self.check_borrows();
         // Safety: We have shared access to self and we only return it as a shared reference.
         unsafe { &*self.get_ptr() }
     }
     /// Returns a `&mut T` to the wrapped `T`.
     pub fn get_mut(&mut self) -> &mut T {
-        self.0.get_mut()
+        self.check_borrows();
+        self.value.get_mut()
+    }
+    /// Runs `f` with a `&mut T` to the wrapped `T`, returning its result.
+    pub fn map_mut<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(self.get_mut())
+    }
+    /// Runs `f` with a `&T` to the wrapped `T`, returning its result.
+    pub fn map_ref<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(self.get())
+    }
+    /// Returns the `&U` that `f` projects the wrapped `T` to, e.g. one of its fields.
+    pub fn project_ref<U: ?Sized>(&self, f: impl FnOnce(&T) -> &U) -> &U {
+        f(self.get())
+    }
+    /// Returns this `&NearSafeCell<T>` unchanged, making an explicit reborrow visible in generic code.
+    pub fn reborrow(&self) -> &NearSafeCell<T> {
+        self
+    }
+    /// Returns this `&NearSafeCell<T>` unchanged, to be handed to each of multiple consumers sharing the cell.
+    /// Shared references are [`Copy`], so this is identical to [`Self::reborrow`] and only spells out the sharing.
+    pub fn shared_ref(&self) -> &NearSafeCell<T> {
+        self
+    }
+    /// Downgrades this `&mut NearSafeCell<T>` to a `&NearSafeCell<T>`, e.g. for passing it to APIs expecting shared access.
+    pub fn as_shared(&mut self) -> &NearSafeCell<T> {
+        self
+    }
+    /// Returns a `&T` to the wrapped `T`, same as [`Self::get`].
+    ///
+    /// Prefer this over relying on [`Deref`] when `T` has methods that share a name with ones on [`NearSafeCell`],
+    /// e.g. `cell.inner().len()` always calls `T::len`, no matter which methods [`NearSafeCell`] gains.
+    pub fn inner(&self) -> &T {
+        self.get()
+    }
+    /// Returns a `&mut T` to the wrapped `T`, same as [`Self::get_mut`].
+    ///
+    /// Prefer this over relying on [`DerefMut`] for the same reasons as [`Self::inner`].
+    pub fn inner_mut(&mut self) -> &mut T {
+        self.get_mut()
+    }
+    /// Returns a `&UnsafeCell<T>` to the [`UnsafeCell`] backing this [`NearSafeCell`], e.g. for handing it to APIs expecting one.
+    /// Without the `near_safe_cell_assert_single_thread` cfg, `NearSafeCell::from_unsafe_cell_ref` goes in the opposite direction.
+    /// Not available with the `loom` cfg, as the cell is then backed by loom's instead.
+    #[cfg(not(loom))]
+    pub const fn as_unsafe_cell(&self) -> &UnsafeCell<T> {
+        self.value.as_unsafe_cell()
+    }
+    const_unless_loom! {
+        /// Returns a `*const T` to the wrapped `T`.
+        ///
+        /// With the `loom` cfg, this counts as an immutable access to the wrapped `T` at the time of the call.
+        pub fn get_ptr(&self) -> *const T {
+            self.value.get_const()
+        }
+    }
+    const_unless_loom! {
+        /// Returns a `*mut T` to the wrapped `T`.
+        ///
+        /// With the `loom` cfg, this counts as a mutable access to the wrapped `T` at the time of the call.
+        pub fn get_mut_ptr(&self) -> *mut T {
+            self.value.get()
+        }
+    }
+    /// Formats the address of the wrapped `T` like [`Pointer`] would, regardless of whether `T` itself implements it.
+    /// # Errors
+    /// Returns an error if writing to `f` fails.
+    pub fn fmt_cell_ptr(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Pointer::fmt(&self.get_ptr(), f)
+    }
+    /// Returns a [`Debug`] formatting like this [`NearSafeCell`]s own, but printing `NearSafeCell(<panicked>)` instead of propagating a panic from `T`s [`Debug`].
+    #[cfg(feature = "std")]
+    pub fn debug_catch(&self) -> impl Debug + '_
+    where
+        T: Debug,
+    {
+        struct DebugCatch<'a, T: ?Sized>(&'a T);
+        impl<T: Debug + ?Sized> Debug for DebugCatch<'_, T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let value = self.0;
+                let alternate = f.alternate();
+                let formatted = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    if alternate {
+                        std::format!("{value:#?}")
+                    } else {
+                        std::format!("{value:?}")
+                    }
+                }));
+                let formatted = formatted.as_deref().unwrap_or("<panicked>");
+                f.debug_tuple("NearSafeCell")
+                    .field(&format_args!("{formatted}"))
+                    .finish()
+            }
+        }
+        DebugCatch(self.get())
+    }
+    /// Issues a [release fence](core::sync::atomic::fence), publishing all prior writes to the wrapped `T`.
+    ///
+    /// Call this after mutating the wrapped `T` and before the atomic store that signals other threads,
+    /// pairing it with a [`Self::consume`] after the atomic load that observes that store.
+    /// With the `near_safe_cell_tsan` cfg this also tells the thread sanitizer about the release, which it cannot infer from fences alone.
+    /// With the `near_safe_cell_assert_single_thread` cfg this also allows the next mutable access to come from another thread.
+    pub fn publish(&self) {
+        #[cfg(near_safe_cell_assert_single_thread)]
+        {
+            *self.owner.lock().unwrap_or_else(PoisonError::into_inner) = None;
+        }
+        #[cfg(near_safe_cell_tsan)]
+        // Safety: The annotation only records the address, it never dereferences it.
+        unsafe {
+            tsan::__tsan_release(self.get_mut_ptr().cast());
+        }
+        fence(Ordering::Release);
+    }
+    /// Issues an [acquire fence](core::sync::atomic::fence), making all writes published by a paired [`Self::publish`] visible.
+    ///
+    /// Call this after the atomic load that observed the store following that [`Self::publish`] and before reading the wrapped `T`.
+    /// With the `near_safe_cell_tsan` cfg this also tells the thread sanitizer about the acquire, which it cannot infer from fences alone.
+    pub fn consume(&self) {
+        fence(Ordering::Acquire);
+        #[cfg(near_safe_cell_tsan)]
+        // Safety: The annotation only records the address, it never dereferences it.
+        unsafe {
+            tsan::__tsan_acquire(self.get_mut_ptr().cast());
+        }
+    }
+    /// Records the current thread as the one mutably accessing the wrapped `T`,
+    /// panicking if another thread did so since the last [`Self::publish`].
+    #[cfg(near_safe_cell_assert_single_thread)]
+    fn assert_single_thread(&self) {
+        let current = std::thread::current().id();
+        let mut owner = self.owner.lock().unwrap_or_else(PoisonError::into_inner);
+        match *owner {
+            Some(previous) if previous != current => {
+                drop(owner);
+                panic!(
+                    "NearSafeCell mutably accessed from {:?} after {:?} without an intervening `publish`",
+                    current, previous
+                );
+            }
+            _ => *owner = Some(current),
+        }
+    }
+    #[cfg(not(near_safe_cell_assert_single_thread))]
+    #[allow(clippy::unused_self)]
+    #[inline(always)]
+    fn assert_single_thread(&self) {}
+    /// Returns `true` if both cells wrap equal `T`s, skipping the potentially expensive value comparison if they are the same cell.
+    /// Only offered for `T: Eq`, as for types like `f64` a value need not be equal to itself.
+    pub fn fast_eq(&self, other: &Self) -> bool
+    where
+        T: Eq,
+    {
+        core::ptr::eq(self, other) || self.get() == other.get()
+    }
+    /// Returns a fingerprint of the wrapped `T`, hashed with a fixed-seed FNV-1a hasher.
+    /// Comparing the fingerprints of two reads cheaply detects whether the value changed in between.
+    /// It is stable within a program, but not guaranteed to be across platforms or compiler versions.
+    pub fn fingerprint(&self) -> u64
+    where
+        T: Hash,
+    {
+        let mut hasher = Fnv1a::default();
+        self.get().hash(&mut hasher);
+        hasher.finish()
+    }
+    /// Returns a new, independent [`NearSafeCell`] wrapping a clone of the current `T`.
+    #[must_use]
+    pub fn snapshot(&self) -> NearSafeCell<T>
+    where
+        T: Clone,
+    {
+        Self::new(self.get().clone())
+    }
+}
+
+impl<T: ?Sized> NearSafeCell<T> {
+    /// Returns a `&U` to the element at index `i` of the slice wrapped in `T`, or [`None`] if it is out of bounds.
+    pub fn try_elem<U>(&self, i: usize) -> Option<&U>
+    where
+        T: AsRef<[U]>,
+    {
+        self.get().as_ref().get(i)
+    }
+    /// Returns a `&[U; N]` to the first `N` elements of the slice wrapped in `T`, or [`None`] if it is shorter than `N`.
+    pub fn as_array_ref<U, const N: usize>(&self) -> Option<&[U; N]>
+    where
+        T: AsRef<[U]>,
+    {
+        self.get().as_ref().first_chunk()
+    }
+    /// Runs `f` with a `&mut [U]` to the `range` of the slice wrapped in `T`, returning its result.
+    /// # Panics
+    /// Panics if `range` is out of bounds of the wrapped slice.
+    /// # Safety
+    /// There exists no other `&T` or `&mut T` to the wrapped `T` currently, nor to any element within `range` while `f` runs.
+    pub unsafe fn with_range_mut<U, R>(
+        &self,
+        range: Range<usize>,
+        f: impl FnOnce(&mut [U]) -> R,
+    ) -> R
+    where
+        T: AsMut<[U]>,
+    {
+        f(&mut self.get_mut_unsafe().as_mut()[range])
+    }
+    /// Returns a `&mut [U]` to the `range` of the slice wrapped in `T`, accepting any kind of range, e.g. `..`, `a..`, `..=b` or `a..b`.
+    /// # Panics
+    /// Panics if `range` is out of bounds of the wrapped slice or its start lies after its end.
+    /// # Safety
+    /// There exists no other `&T` or `&mut T` to the wrapped `T` currently, nor to any element within `range` until the returned `&mut [U]` is dropped.
+    pub unsafe fn slice_mut_unsafe<U, R: RangeBounds<usize>>(&self, range: R) -> &mut [U]
+    where
+        T: AsMut<[U]>,
+    {
+        let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+        &mut self.get_mut_unsafe().as_mut()[bounds]
+    }
+    /// Returns an iterator over `&mut [U]` chunks of `size` elements of the slice wrapped in `T`, see [`slice::chunks_mut`].
+    /// # Panics
+    /// Panics if `size` is 0.
+    /// # Safety
+    /// There exists no other `&T` or `&mut T` to the wrapped `T` currently and until the returned iterator and all chunks it yielded are dropped.
+    pub unsafe fn chunks_mut_unsafe<U>(&self, size: usize) -> ChunksMut<'_, U>
+    where
+        T: AsMut<[U]>,
+    {
+        self.get_mut_unsafe().as_mut().chunks_mut(size)
+    }
+    /// Swaps the elements at indices `a` and `b` of the slice wrapped in `T`, see [`slice::swap`].
+    /// # Panics
+    /// Panics if `a` or `b` are out of bounds.
+    /// # Safety
+    /// There exists no other `&T` or `&mut T` to the wrapped `T` currently.
+    pub unsafe fn swap_unsafe_at<U>(&self, a: usize, b: usize)
+    where
+        T: AsMut<[U]>,
+    {
+        self.get_mut_unsafe().as_mut().swap(a, b);
+    }
+    /// Returns an iterator over `(index, &mut U)` pairs of the elements of the slice wrapped in `T`.
+    /// # Safety
+    /// There exists no other `&T` or `&mut T` to the wrapped `T` currently and until the returned iterator and all elements it yielded are dropped.
+    pub unsafe fn iter_mut_enumerate_unsafe<'a, U: 'a>(
+        &'a self,
+    ) -> impl Iterator<Item = (usize, &'a mut U)> + 'a
+    where
+        T: AsMut<[U]>,
+    {
+        self.get_mut_unsafe().as_mut().iter_mut().enumerate()
+    }
+    /// Reinterprets the byte slice wrapped in `T` as an unaligned prefix, an aligned middle of `U`s and an unaligned suffix, see [`slice::align_to_mut`].
+    /// # Safety
+    /// There exists no other `&T` or `&mut T` to the wrapped `T` currently and until the returned slices are dropped,
+    /// and any bit pattern of the middle bytes is a valid `U`, see [`slice::align_to_mut`].
+    pub unsafe fn align_to_mut<U>(&self) -> (&mut [u8], &mut [U], &mut [u8])
+    where
+        T: AsMut<[u8]>,
+    {
+        self.get_mut_unsafe().as_mut().align_to_mut()
+    }
+    /// Fills the slice wrapped in `T` with elements returned by calling `f` repeatedly, see [`slice::fill_with`].
+    /// # Safety
+    /// There exists no other `&T` or `&mut T` to the wrapped `T` currently.
+    pub unsafe fn fill_with_unsafe<U>(&self, f: impl FnMut() -> U)
+    where
+        T: AsMut<[U]>,
+    {
+        self.get_mut_unsafe().as_mut().fill_with(f);
+    }
+    /// Returns two `&mut Vec`s to distinct fields of the wrapped `T`, projected from its pointer by `fa` and `fb`.
+    /// Useful for mutating multiple component storages of the same struct at once without borrowing the whole `T`.
+    /// # Safety
+    /// `fa` and `fb` return valid pointers to two distinct, non-overlapping [`Vec`]s within the wrapped `T`,
+    /// and there exists no other `&T` or `&mut T` to the wrapped `T`, nor to either of those [`Vec`]s, currently and until the returned references are dropped.
+    #[cfg(feature = "alloc")]
+    pub unsafe fn two_vecs_mut<A, B>(
+        &self,
+        fa: impl FnOnce(*mut T) -> *mut Vec<A>,
+        fb: impl FnOnce(*mut T) -> *mut Vec<B>,
+    ) -> (&mut Vec<A>, &mut Vec<B>) {
+        let ptr: *mut T = self.get_mut_unsafe();
+        (&mut *fa(ptr), &mut *fb(ptr))
+    }
+    /// Fills the slice wrapped in `T` with clones of `value` in parallel using [`rayon`].
+    /// # Safety
+    /// There exists no other `&T` or `&mut T` to the wrapped `T` currently.
+    #[cfg(feature = "rayon")]
+    pub unsafe fn par_fill<U>(&self, value: U)
+    where
+        T: AsMut<[U]>,
+        U: Clone + Send + Sync,
+    {
+        use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
+        self.get_mut_unsafe()
+            .as_mut()
+            .par_iter_mut()
+            .for_each_with(value, |value, elem| *elem = value.clone());
+    }
+}
+
+if_transparent! {
+    impl<T: ?Sized> NearSafeCell<T> {
+        /// Reinterprets a `&UnsafeCell<T>` as a `&NearSafeCell<T>`, granting its ergonomics without taking ownership.
+        pub const fn from_unsafe_cell_ref(cell: &UnsafeCell<T>) -> &NearSafeCell<T> {
+            // Safety: `NearSafeCell<T>` is `#[repr(transparent)]` over `UnsafeCell<T>`.
+            unsafe { &*(core::ptr::from_ref(cell) as *const NearSafeCell<T>) }
+        }
+        /// Reinterprets an exclusive `&mut T` as a `&NearSafeCell<T>`, e.g. for sharing it with code expecting a cell for a sub-scope.
+        /// Like [`UnsafeCell::from_mut`], once the returned reference is dropped the original `&mut T` observes all writes made through it.
+        pub const fn from_mut(value: &mut T) -> &NearSafeCell<T> {
+            // Safety: `NearSafeCell<T>` is `#[repr(transparent)]` over `T`, and the exclusive borrow rules out any other access while the cell is alive.
+            unsafe { &*(core::ptr::from_mut(value) as *const NearSafeCell<T>) }
+        }
+        /// Returns a `*mut T` to the `T` wrapped by the cell `this` points to, without ever creating a reference to it, see [`UnsafeCell::raw_get`].
+        /// Allows e.g. initializing a [`MaybeUninit<NearSafeCell<T>>`](core::mem::MaybeUninit) in place.
+        pub const fn raw_get(this: *const Self) -> *mut T {
+            UnsafeCell::raw_get(this as *const UnsafeCell<T>)
+        }
+    }
+
+    impl<T> NearSafeCell<[T]> {
+        /// Reinterprets this `&NearSafeCell<[T]>` as a `&[NearSafeCell<T>]`, see [`core::cell::Cell::as_slice_of_cells`].
+        pub fn as_slice_of_cells(&self) -> &[NearSafeCell<T>] {
+            // Safety: `NearSafeCell<T>` is `#[repr(transparent)]` over `T`, so both are slices of identically laid out elements.
+            unsafe { &*(core::ptr::from_ref(self) as *const [NearSafeCell<T>]) }
+        }
+    }
+
+    impl<T, const N: usize> NearSafeCell<[T; N]> {
+        /// Reinterprets this `&NearSafeCell<[T; N]>` as a `&[NearSafeCell<T>; N]`, see [`core::cell::Cell::as_array_of_cells`].
+        pub fn as_array_of_cells(&self) -> &[NearSafeCell<T>; N] {
+            // Safety: `NearSafeCell<T>` is `#[repr(transparent)]` over `T`, so both are arrays of identically laid out elements.
+            unsafe { &*(core::ptr::from_ref(self).cast::<[NearSafeCell<T>; N]>()) }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    use alloc::boxed::Box;
+    #[cfg(feature = "alloc")]
+    impl<T: ?Sized> NearSafeCell<T> {
+        /// Reinterprets a `Box<T>` as a `Box<NearSafeCell<T>>` in place, without moving or copying the `T`.
+        /// Unlike converting a `Box<T>` into a `NearSafeCell<Box<T>>`, this wraps the boxed `T` itself instead of the [`Box`].
+        #[must_use]
+        pub fn from_box(boxed: Box<T>) -> Box<NearSafeCell<T>> {
+            // Safety: `NearSafeCell<T>` is `#[repr(transparent)]` over `T`, so the allocation fits either type.
+            unsafe { Box::from_raw(Box::into_raw(boxed) as *mut NearSafeCell<T>) }
+        }
+        /// Reinterprets a `Box<NearSafeCell<T>>` as a `Box<T>` in place, without moving or copying the `T`.
+        #[must_use]
+        pub fn into_box(boxed: Box<Self>) -> Box<T> {
+            // Safety: `NearSafeCell<T>` is `#[repr(transparent)]` over `T`, so the allocation fits either type.
+            unsafe { Box::from_raw(Box::into_raw(boxed) as *mut T) }
+        }
+    }
+
+    // Safety: `NearSafeCell<T>` is `#[repr(transparent)]` over `T`, so it is all zeroes exactly when `T` is.
+    #[cfg(feature = "bytemuck")]
+    unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for NearSafeCell<T> {}
+
+    // `NearSafeCell<T>` must stay layout compatible with `T` for the pointers it hands out to be usable across FFI.
+    const _: () = assert!(
+        core::mem::size_of::<NearSafeCell<u32>>() == core::mem::size_of::<u32>()
+            && core::mem::align_of::<NearSafeCell<u32>>() == core::mem::align_of::<u32>()
+    );
+}
+
+impl<T> NearSafeCell<[T]> {
+    /// Returns a [`NonNull<[T]>`](NonNull) to the wrapped slice, preserving its length.
+    pub fn as_non_null_slice(&self) -> NonNull<[T]> {
+        // Safety: The pointer returned by `ValueCell::get` is derived from a reference and therefore never null.
+        unsafe { NonNull::new_unchecked(self.value.get()) }
+    }
+    /// Returns `&mut [T]`s to the two halves of the wrapped slice, split at `mid`, see [`slice::split_at_mut`].
+    /// # Panics
+    /// Panics if `mid > len`.
+    /// # Safety
+    /// There exists no other `&[T]` or `&mut [T]` to the wrapped slice, nor to any of its elements, currently and until both returned halves are dropped.
+    pub unsafe fn split_at_mut_unchecked(&self, mid: usize) -> (&mut [T], &mut [T]) {
+        self.get_mut_unsafe().split_at_mut(mid)
+    }
+}
+
+impl<T, const N: usize> NearSafeCell<[T; N]> {
+    /// Returns the number of elements in the wrapped array.
+    pub const fn len(&self) -> usize {
+        N
+    }
+    /// Returns `true` if the wrapped array has a length of 0.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+    /// Returns a `&mut [T; N]` to the whole wrapped array, bypassing the borrow checker.
+    /// # Safety
+    /// There exists no other `&[T; N]` or `&mut [T; N]` to the wrapped array, nor to any of its elements, currently and until the returned `&mut [T; N]` is dropped.
+    pub unsafe fn get_mut_unsafe_array(&self) -> &mut [T; N] {
+        self.get_mut_unsafe()
+    }
+    /// Replaces the whole wrapped array with `new`, returning the previous one.
+    /// # Safety
+    /// There exists no other `&[T; N]` or `&mut [T; N]` to the wrapped array, nor to any of its elements, currently.
+    pub unsafe fn replace_array_unsafe(&self, new: [T; N]) -> [T; N] {
+        core::mem::replace(self.get_mut_unsafe(), new)
+    }
+}
+
+impl<T> NearSafeCell<MaybeUninit<T>> {
+    /// Returns a `&mut T` to the initialized value wrapped in the [`MaybeUninit<T>`], bypassing the borrow checker.
+    /// # Safety
+    /// The wrapped [`MaybeUninit<T>`] is initialized, see [`MaybeUninit::assume_init_mut`],
+    /// and there exists no other `&MaybeUninit<T>`, `&mut MaybeUninit<T>`, `&T` or `&mut T` to it currently and until the returned `&mut T` is dropped.
+    pub unsafe fn assume_init_get_mut_unsafe(&self) -> &mut T {
+        self.get_mut_unsafe().assume_init_mut()
+    }
+    /// Initializes the wrapped [`MaybeUninit<T>`] with `val`, returning a `&mut T` to it, see [`MaybeUninit::write`].
+    /// Any previously initialized value is overwritten without being dropped.
+    pub fn write(&mut self, val: T) -> &mut T {
+        self.get_mut().write(val)
+    }
+}
+
+/// Implements `const_eq` for cells wrapping each of the given primitive types.
+macro_rules! impl_const_eq {
+    ($($ty:ty),*) => {$(
+        impl NearSafeCell<$ty> {
+            const_unless_loom! {
+                #[doc = concat!("Returns `true` if the wrapped `", stringify!($ty), "` equals `other`, usable in `const` contexts unlike [`PartialEq`].")]
+                pub fn const_eq(&self, other: &$ty) -> bool {
+                    // Safety: Same as for `Self::get`, just without requiring the non-const `PartialEq`.
+                    unsafe { *self.get_ptr() == *other }
+                }
+            }
+        }
+    )*};
+}
+impl_const_eq!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, char, bool);
+
+impl NearSafeCell<u64> {
+    /// Sets the wrapped value to the maximum of itself and `val`, returning the previous value.
+    /// # Safety
+    /// There exists no other `&u64` or `&mut u64` to the wrapped value currently.
+    pub unsafe fn fetch_max(&self, val: u64) -> u64 {
+        let current = self.get_mut_unsafe();
+        let previous = *current;
+        *current = previous.max(val);
+        previous
+    }
+    /// Sets the wrapped value to the minimum of itself and `val`, returning the previous value.
+    /// # Safety
+    /// There exists no other `&u64` or `&mut u64` to the wrapped value currently.
+    pub unsafe fn fetch_min(&self, val: u64) -> u64 {
+        let current = self.get_mut_unsafe();
+        let previous = *current;
+        *current = previous.min(val);
+        previous
+    }
+}
+
+impl<I: Iterator> NearSafeCell<I> {
+    /// Consumes this [`NearSafeCell`], summing up the items of the wrapped [`Iterator`], see [`Iterator::sum`].
+    pub fn sum<S: core::iter::Sum<I::Item>>(self) -> S {
+        self.unwrap().sum()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> NearSafeCell<Vec<T>> {
+    /// Returns the number of elements in the wrapped [`Vec`], without having to go through [`Deref`].
+    pub fn len(&self) -> usize {
+        self.get().len()
+    }
+    /// Returns `true` if the wrapped [`Vec`] contains no elements, without having to go through [`Deref`].
+    pub fn is_empty(&self) -> bool {
+        self.get().is_empty()
+    }
+    /// Removes and returns the element at `index` of the wrapped [`Vec`], replacing it with the last element, see [`Vec::swap_remove`].
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        self.get_mut().swap_remove(index)
+    }
+    /// Retains only the elements of the wrapped [`Vec`] for which `f` returns `true`, see [`Vec::retain`].
+    /// # Safety
+    /// There exists no other `&Vec<T>` or `&mut Vec<T>` to the wrapped [`Vec`], nor to any of its elements, currently.
+    pub unsafe fn retain_unsafe(&self, f: impl FnMut(&T) -> bool) {
+        self.get_mut_unsafe().retain(f);
+    }
+    /// Appends clones of all elements of `data` to the wrapped [`Vec`], see [`Vec::extend_from_slice`].
+    /// # Safety
+    /// There exists no other `&Vec<T>` or `&mut Vec<T>` to the wrapped [`Vec`], nor to any of its elements, currently.
+    pub unsafe fn extend_from_slice_unsafe(&self, data: &[T])
+    where
+        T: Clone,
+    {
+        self.get_mut_unsafe().extend_from_slice(data);
+    }
+    /// Inserts `element` at `index` of the wrapped [`Vec`], shifting all elements after it to the right, see [`Vec::insert`].
+    /// # Panics
+    /// Panics if `index > len`.
+    /// # Safety
+    /// There exists no other `&Vec<T>` or `&mut Vec<T>` to the wrapped [`Vec`], nor to any of its elements, currently.
+    pub unsafe fn insert_unsafe(&self, index: usize, element: T) {
+        self.get_mut_unsafe().insert(index, element);
     }
-    /// Returns a `*const T` to the wrapped `T`.
-    pub const fn get_ptr(&self) -> *const T {
-        self.0.get()
+    /// Inserts `element` into the sorted wrapped [`Vec`] after all elements less than or equal to it, keeping it sorted, and returns its index.
+    /// # Safety
+    /// There exists no other `&Vec<T>` or `&mut Vec<T>` to the wrapped [`Vec`], nor to any of its elements, currently.
+    pub unsafe fn insert_sorted_unsafe(&self, element: T) -> usize
+    where
+        T: Ord,
+    {
+        let vec = self.get_mut_unsafe();
+        let index = vec.partition_point(|e| *e <= element);
+        vec.insert(index, element);
+        index
+    }
+    /// Removes consecutive repeated elements from the wrapped [`Vec`], see [`Vec::dedup`].
+    /// # Safety
+    /// There exists no other `&Vec<T>` or `&mut Vec<T>` to the wrapped [`Vec`], nor to any of its elements, currently.
+    pub unsafe fn dedup_unsafe(&self)
+    where
+        T: PartialEq,
+    {
+        self.get_mut_unsafe().dedup();
+    }
+    /// Shortens the wrapped [`Vec`] to `len` elements, see [`Vec::truncate`].
+    /// # Safety
+    /// There exists no other `&Vec<T>` or `&mut Vec<T>` to the wrapped [`Vec`], nor to any of its elements, currently.
+    pub unsafe fn truncate_unsafe(&self, len: usize) {
+        self.get_mut_unsafe().truncate(len);
+    }
+    /// Removes the elements in `range` from the wrapped [`Vec`], returning them as an iterator, see [`Vec::drain`].
+    /// # Panics
+    /// Panics if the start of `range` is greater than its end or its end is greater than the length of the [`Vec`].
+    pub fn drain_iter(&mut self, range: Range<usize>) -> alloc::vec::Drain<'_, T> {
+        self.get_mut().drain(range)
+    }
+    /// Consumes this [`NearSafeCell`], mapping each element of the wrapped [`Vec`] with `f` and collecting the results into a `C`.
+    pub fn map_collect<U, C: FromIterator<U>>(self, f: impl FnMut(T) -> U) -> C {
+        self.unwrap().into_iter().map(f).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl NearSafeCell<String> {
+    /// Shortens the wrapped [`String`] to `len` bytes, see [`String::truncate`].
+    /// # Panics
+    /// Panics if `len` does not lie on a [`char`] boundary.
+    /// # Safety
+    /// There exists no other `&String` or `&mut String` to the wrapped [`String`], nor to any part of it, currently.
+    pub unsafe fn truncate_unsafe(&self, len: usize) {
+        self.get_mut_unsafe().truncate(len);
+    }
+}
+
+/// Minimal FNV-1a [`Hasher`] backing [`NearSafeCell::fingerprint`] without requiring `std`.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
     }
-    /// Returns a `*mut T` to the wrapped `T`.
-    pub const fn get_mut_ptr(&self) -> *mut T {
-        self.0.get()
+}
+
+/// Returns the `T` wrapped in `arc` if it is the only strong reference to its [`NearSafeCell`], see [`Arc::try_unwrap`].
+/// # Errors
+/// Returns `arc` unchanged if there are other strong references to its [`NearSafeCell`].
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+pub fn try_unwrap<T>(arc: Arc<NearSafeCell<T>>) -> Result<T, Arc<NearSafeCell<T>>> {
+    Arc::try_unwrap(arc).map(NearSafeCell::unwrap)
+}
+
+/// Consumes all `cells`, collecting their wrapped values into a [`HashSet`], dropping duplicates.
+#[cfg(feature = "std")]
+pub fn collect_set<T: Hash + Eq>(cells: impl IntoIterator<Item = NearSafeCell<T>>) -> HashSet<T> {
+    cells.into_iter().map(NearSafeCell::unwrap).collect()
+}
+
+/// Runs `f` with each element of the slice wrapped in `dst` and the element at the same index of the slice wrapped in `src`.
+/// # Panics
+/// Panics if the wrapped slices differ in length.
+/// # Safety
+/// There exists no other `&[U]` or `&mut [U]` to the slice wrapped in `dst`, nor to any of its elements, currently,
+/// and no `&mut [V]` to the slice wrapped in `src`, nor to any of its elements, currently, meaning `dst` and `src` do not overlap either.
+pub unsafe fn zip_apply<U, V>(
+    dst: &NearSafeCell<[U]>,
+    src: &NearSafeCell<[V]>,
+    f: impl Fn(&mut U, &V),
+) {
+    let (dst, src) = (dst.get_mut_unsafe(), src.get());
+    assert_eq!(
+        dst.len(),
+        src.len(),
+        "zip_apply called with slices of different lengths"
+    );
+    for (d, s) in dst.iter_mut().zip(src) {
+        f(d, s);
     }
 }
 
@@ -79,93 +926,345 @@ impl<T> NearSafeCell<T> {
 // The only way this impl could be unsafe would be if we
 // violated [`NearSafeCell::get_mut_unsafe`](NearSafeCell::get_mut_unsafe)s safety requirements,
 // at which point the fault lies with us and not this impl.
-unsafe impl<T: Sync> Sync for NearSafeCell<T> {}
+unsafe impl<T: ?Sized + Sync> Sync for NearSafeCell<T> {}
 
 // # Safety
 // The only way this impl could be unsafe would be if we
 // violated [`NearSafeCell::get_mut_unsafe`](NearSafeCell::get_mut_unsafe)s safety requirements,
 // at which point the fault lies with us and not this impl.
 #[cfg(feature = "std")]
-use std::panic::RefUnwindSafe;
-#[cfg(feature = "std")]
-unsafe impl<T: RefUnwindSafe> RefUnwindSafe for NearSafeCell<T> {}
+impl<T: ?Sized + std::panic::RefUnwindSafe> std::panic::RefUnwindSafe for NearSafeCell<T> {}
 
-impl<T> AsRef<T> for NearSafeCell<T> {
-    fn as_ref(&self) -> &T {
-        self.get()
-    }
+/// Lets a cell wrapping e.g. a `&[T; N]` coerce to one wrapping a `&[T]`, just like [`UnsafeCell`] does.
+#[cfg(all(feature = "unsize", not(loom)))]
+impl<T: core::ops::CoerceUnsized<U>, U> core::ops::CoerceUnsized<NearSafeCell<U>>
+    for NearSafeCell<T>
+{
 }
-impl<T> AsMut<T> for NearSafeCell<T> {
-    fn as_mut(&mut self) -> &mut T {
-        self.get_mut()
+
+impl<T> From<T> for NearSafeCell<T> {
+    fn from(val: T) -> Self {
+        Self::new(val)
     }
 }
 
-impl<T> Deref for NearSafeCell<T> {
-    type Target = T;
-    fn deref(&self) -> &Self::Target {
-        self.get()
+impl<T> From<UnsafeCell<T>> for NearSafeCell<T> {
+    fn from(cell: UnsafeCell<T>) -> Self {
+        Self::from_unsafe_cell(cell)
     }
 }
-impl<T> DerefMut for NearSafeCell<T> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        self.get_mut()
+impl<T> From<NearSafeCell<T>> for UnsafeCell<T> {
+    fn from(cell: NearSafeCell<T>) -> Self {
+        cell.into_unsafe_cell()
     }
 }
 
-impl<T: Debug> Debug for NearSafeCell<T> {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_tuple("NearSafeCell").field(self.get()).finish()
+/// Consumes the cell, iterating the wrapped `T` by value.
+/// Only the owned form is offered, iterating a shared cell goes explicitly through e.g. `cell.get().iter()`.
+impl<T: IntoIterator> IntoIterator for NearSafeCell<T> {
+    type Item = T::Item;
+    type IntoIter = T::IntoIter;
+    fn into_iter(self) -> Self::IntoIter {
+        self.unwrap().into_iter()
     }
 }
 
-impl<T: Display> Display for NearSafeCell<T> {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        self.get().fmt(f)
+impl<T: ?Sized> AsRef<T> for NearSafeCell<T> {
+    fn as_ref(&self) -> &T {
+        self.get()
     }
 }
-impl<T: Octal> Octal for NearSafeCell<T> {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        self.get().fmt(f)
+
+/// Borrows a [`NearSafeCell<T>`] out of `Self`, for generic code accepting either a cell or something owning or pointing to one.
+///
+/// Unlike a reflexive [`AsRef<NearSafeCell<T>>`], this leaves `cell.as_ref()` inferring [`AsRef<T>`].
+pub trait AsNearSafeCell<T: ?Sized> {
+    /// Returns a `&NearSafeCell<T>` borrowed from `self`.
+    fn as_near_safe_cell(&self) -> &NearSafeCell<T>;
+}
+impl<T: ?Sized> AsNearSafeCell<T> for NearSafeCell<T> {
+    fn as_near_safe_cell(&self) -> &NearSafeCell<T> {
+        self
     }
 }
-impl<T: LowerHex> LowerHex for NearSafeCell<T> {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        self.get().fmt(f)
+impl<T: ?Sized, C: ?Sized + AsNearSafeCell<T>> AsNearSafeCell<T> for &C {
+    fn as_near_safe_cell(&self) -> &NearSafeCell<T> {
+        (**self).as_near_safe_cell()
     }
 }
-impl<T: UpperHex> UpperHex for NearSafeCell<T> {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        self.get().fmt(f)
+impl<T: ?Sized, C: ?Sized + AsNearSafeCell<T>> AsNearSafeCell<T> for &mut C {
+    fn as_near_safe_cell(&self) -> &NearSafeCell<T> {
+        (**self).as_near_safe_cell()
     }
 }
-impl<T: Pointer> Pointer for NearSafeCell<T> {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        self.get().fmt(f)
+#[cfg(feature = "alloc")]
+impl<T: ?Sized, C: ?Sized + AsNearSafeCell<T>> AsNearSafeCell<T> for alloc::boxed::Box<C> {
+    fn as_near_safe_cell(&self) -> &NearSafeCell<T> {
+        (**self).as_near_safe_cell()
     }
 }
-impl<T: Binary> Binary for NearSafeCell<T> {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        self.get().fmt(f)
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<T: ?Sized, C: ?Sized + AsNearSafeCell<T>> AsNearSafeCell<T> for Arc<C> {
+    fn as_near_safe_cell(&self) -> &NearSafeCell<T> {
+        (**self).as_near_safe_cell()
     }
 }
-impl<T: LowerExp> LowerExp for NearSafeCell<T> {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        self.get().fmt(f)
+#[cfg(feature = "alloc")]
+impl AsRef<str> for NearSafeCell<String> {
+    fn as_ref(&self) -> &str {
+        self.get().as_str()
     }
 }
-impl<T: UpperExp> UpperExp for NearSafeCell<T> {
+impl<T: ?Sized> AsMut<T> for NearSafeCell<T> {
+    fn as_mut(&mut self) -> &mut T {
+        self.get_mut()
+    }
+}
+
+impl<T: ?Sized> Borrow<T> for NearSafeCell<T> {
+    fn borrow(&self) -> &T {
+        self.get()
+    }
+}
+impl<U, const N: usize> Borrow<[U]> for NearSafeCell<[U; N]> {
+    fn borrow(&self) -> &[U] {
+        self.get()
+    }
+}
+#[cfg(feature = "alloc")]
+impl Borrow<str> for NearSafeCell<String> {
+    fn borrow(&self) -> &str {
+        self.get()
+    }
+}
+
+impl<T: ?Sized + PartialEq> PartialEq for NearSafeCell<T> {
+    fn eq(&self, other: &Self) -> bool {
+        // No pointer identity fast path here, it would make e.g. a NaN cell equal to itself, see `NearSafeCell::fast_eq`.
+        self.get() == other.get()
+    }
+}
+impl<T: ?Sized + Eq> Eq for NearSafeCell<T> {}
+impl<T: ?Sized + PartialOrd> PartialOrd for NearSafeCell<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.get().partial_cmp(other.get())
+    }
+    fn lt(&self, other: &Self) -> bool {
+        self.get() < other.get()
+    }
+    fn le(&self, other: &Self) -> bool {
+        self.get() <= other.get()
+    }
+    fn gt(&self, other: &Self) -> bool {
+        self.get() > other.get()
+    }
+    fn ge(&self, other: &Self) -> bool {
+        self.get() >= other.get()
+    }
+}
+impl<T: ?Sized + Ord> Ord for NearSafeCell<T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.get().cmp(other.get())
+    }
+}
+
+/// Compares the wrapped `T` with a bare `T`, e.g. `cell == 42`.
+///
+/// The symmetric `42 == cell` would need an `impl PartialEq<NearSafeCell<T>> for T`, which the orphan rules forbid for a generic `T`.
+impl<T: ?Sized + PartialEq> PartialEq<T> for NearSafeCell<T> {
+    fn eq(&self, other: &T) -> bool {
+        self.get() == other
+    }
+}
+impl<U: PartialEq, const N: usize> PartialEq<[U]> for NearSafeCell<[U; N]> {
+    fn eq(&self, other: &[U]) -> bool {
+        self.get()[..] == *other
+    }
+}
+
+impl<T: ?Sized + Hash> Hash for NearSafeCell<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get().hash(state);
+    }
+}
+
+impl<T: ?Sized> Deref for NearSafeCell<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
+impl<T: ?Sized> DerefMut for NearSafeCell<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.get_mut()
+    }
+}
+
+impl<T: Shl<U>, U> Shl<U> for NearSafeCell<T> {
+    type Output = T::Output;
+    fn shl(self, rhs: U) -> Self::Output {
+        self.unwrap() << rhs
+    }
+}
+impl<T: ShlAssign<U>, U> ShlAssign<U> for NearSafeCell<T> {
+    fn shl_assign(&mut self, rhs: U) {
+        *self.get_mut() <<= rhs;
+    }
+}
+impl<T: Shr<U>, U> Shr<U> for NearSafeCell<T> {
+    type Output = T::Output;
+    fn shr(self, rhs: U) -> Self::Output {
+        self.unwrap() >> rhs
+    }
+}
+impl<T: ShrAssign<U>, U> ShrAssign<U> for NearSafeCell<T> {
+    fn shr_assign(&mut self, rhs: U) {
+        *self.get_mut() >>= rhs;
+    }
+}
+
+impl<T: ?Sized + core::fmt::Write> core::fmt::Write for NearSafeCell<T> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.get_mut().write_str(s)
+    }
+    fn write_char(&mut self, c: char) -> core::fmt::Result {
+        self.get_mut().write_char(c)
+    }
+    fn write_fmt(&mut self, args: core::fmt::Arguments<'_>) -> core::fmt::Result {
+        self.get_mut().write_fmt(args)
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T: ?Sized + defmt::Format> defmt::Format for NearSafeCell<T> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        self.get().format(f);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: ?Sized + serde::Serialize> serde::Serialize for NearSafeCell<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.get().serialize(serializer)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for NearSafeCell<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self::new)
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for NearSafeCell<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("NearSafeCell").field(&self.get()).finish()
+    }
+}
+
+impl<T: ?Sized + Display> Display for NearSafeCell<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.get().fmt(f)
+    }
+}
+impl<T: ?Sized + Octal> Octal for NearSafeCell<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.get().fmt(f)
+    }
+}
+impl<T: ?Sized + LowerHex> LowerHex for NearSafeCell<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.get().fmt(f)
+    }
+}
+impl<T: ?Sized + UpperHex> UpperHex for NearSafeCell<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.get().fmt(f)
+    }
+}
+impl<T: ?Sized + Pointer> Pointer for NearSafeCell<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.get().fmt(f)
+    }
+}
+impl<T: ?Sized + Binary> Binary for NearSafeCell<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.get().fmt(f)
+    }
+}
+impl<T: ?Sized + LowerExp> LowerExp for NearSafeCell<T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.get().fmt(f)
     }
 }
+impl<T: ?Sized + UpperExp> UpperExp for NearSafeCell<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+/// Returns a [`Debug`] formatting `cells` as a list of their wrapped values, e.g. `[1, 2, 3]`.
+pub fn debug_cells<T: Debug>(cells: &[NearSafeCell<T>]) -> impl Debug + '_ {
+    struct DebugCells<'a, T>(&'a [NearSafeCell<T>]);
+    impl<T: Debug> Debug for DebugCells<'_, T> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.debug_list()
+                .entries(self.0.iter().map(NearSafeCell::get))
+                .finish()
+        }
+    }
+    DebugCells(cells)
+}
+
+/// Defines a global [`NearSafeCell`] singleton, accessed through a function of the given name.
+///
+/// `define_cell_static!(pub COUNTER: u32 = 0);` expands to a `pub fn COUNTER() -> &'static NearSafeCell<u32>`
+/// returning a `static` cell initialized with `0`, as a `static` and a `fn` cannot share the same name.
+/// Mutating the singleton still goes through [`NearSafeCell::get_mut_unsafe`] and thus requires external synchronization.
+#[macro_export]
+macro_rules! define_cell_static {
+    ($(#[$attr:meta])* $vis:vis $name:ident: $ty:ty = $init:expr $(;)?) => {
+        $(#[$attr])*
+        #[allow(non_snake_case)]
+        $vis fn $name() -> &'static $crate::NearSafeCell<$ty> {
+            static CELL: $crate::NearSafeCell<$ty> = $crate::NearSafeCell::new($init);
+            &CELL
+        }
+    };
+}
+
+/// Asserts that the value wrapped in a [`NearSafeCell`] equals `expected`, like [`assert_eq!`] on [`NearSafeCell::get`].
+///
+/// On failure the panic message shows both the wrapped value and `expected` using their [`Debug`] implementations.
+#[macro_export]
+macro_rules! assert_cell_eq {
+    ($cell:expr, $expected:expr $(,)?) => {
+        match ($crate::NearSafeCell::get(&$cell), &$expected) {
+            (cell, expected) => {
+                if *cell != *expected {
+                    ::core::panic!(
+                        "assertion `cell == expected` failed\n    cell: {:?}\nexpected: {:?}",
+                        cell,
+                        expected
+                    );
+                }
+            }
+        }
+    };
+}
 
-#[cfg(test)]
+#[cfg(all(test, not(loom)))]
 mod tests {
+    extern crate std;
     use super::*;
     use core::ops::{Deref, DerefMut};
 
     #[test]
+    // Predates these lints and is kept as written.
+    #[allow(
+        dropping_references,
+        clippy::explicit_deref_methods,
+        clippy::ref_as_ptr
+    )]
     fn usage() {
         let mut cell = NearSafeCell::<usize>::default();
         assert_eq!(cell.get(), &usize::default());
@@ -212,8 +1311,226 @@ mod tests {
         assert_eq!(cell.unwrap(), [24, 42, 3, 4, 5]);
     }
 
+    #[test]
+    fn with_mut_then() {
+        let cell = NearSafeCell::new(41u32);
+        let (old, new) = unsafe {
+            cell.with_mut_then(
+                |v| {
+                    *v += 1;
+                    *v - 1
+                },
+                |v| *v,
+            )
+        };
+        assert_eq!((old, new), (41, 42));
+        assert_eq!(cell.get(), &42);
+    }
+
+    #[test]
+    fn with_value() {
+        struct Config {
+            retries: NearSafeCell<u8>,
+        }
+        let config = Config {
+            retries: NearSafeCell::<u8>::default().with_value(3),
+        };
+        assert_eq!(config.retries.get(), &3);
+    }
+
+    #[test]
+    fn snapshot() {
+        let shared = std::sync::Arc::new(NearSafeCell::new(24));
+        let snapshot = shared.snapshot();
+        unsafe { *shared.get_mut_unsafe() = 42 };
+        assert_eq!(shared.get(), &42);
+        assert_eq!(snapshot.get(), &24);
+    }
+
+    #[test]
+    fn fast_eq() {
+        // Self-comparison short-circuits on pointer identity instead of comparing all 1024 bytes.
+        let cell = NearSafeCell::new([7u8; 1024]);
+        assert!(cell.fast_eq(&cell));
+        assert!(cell.fast_eq(&NearSafeCell::new([7u8; 1024])));
+        assert!(!cell.fast_eq(&NearSafeCell::new([8u8; 1024])));
+    }
+
+    #[test]
+    #[allow(clippy::eq_op)]
+    fn float_comparisons() {
+        let nan = NearSafeCell::new(f64::NAN);
+        assert!(nan != nan);
+        assert!(nan != NearSafeCell::new(f64::NAN));
+        assert_eq!(nan.partial_cmp(&nan), None);
+        assert_eq!([nan < nan, nan <= nan, nan > nan, nan >= nan], [false; 4]);
+        assert!(NearSafeCell::new(-0.0) == NearSafeCell::new(0.0));
+        assert!(NearSafeCell::new(f64::NEG_INFINITY) < NearSafeCell::new(f64::MIN));
+        assert!(NearSafeCell::new(f64::INFINITY) > NearSafeCell::new(f64::MAX));
+        assert!(NearSafeCell::new(1.0) <= NearSafeCell::new(1.0));
+    }
+
+    #[test]
+    fn try_elem() {
+        let cell = NearSafeCell::new([1, 2, 3]);
+        assert_eq!(cell.try_elem(0), Some(&1));
+        assert_eq!(cell.try_elem(2), Some(&3));
+        assert_eq!(cell.try_elem(3), None);
+    }
+
+    #[test]
+    fn fill_with() {
+        let cell = NearSafeCell::new([0u32; 4]);
+        let mut counter = 0;
+        unsafe {
+            cell.fill_with_unsafe(|| {
+                counter += 1;
+                counter
+            });
+        }
+        assert_eq!(cell.get(), &[1, 2, 3, 4]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_fill() {
+        let cell = NearSafeCell::new(std::vec![0u32; 1 << 16]);
+        unsafe { cell.par_fill(42) };
+        assert!(cell.get().iter().all(|&v| v == 42));
+    }
+
+    #[test]
+    fn non_null_slice() {
+        let cell: &NearSafeCell<[u8]> = &NearSafeCell::new([1, 2, 3]);
+        let ptr = cell.as_non_null_slice();
+        assert_eq!(ptr.len(), 3);
+        assert_eq!(unsafe { ptr.as_ref() }, [1, 2, 3]);
+    }
+
+    #[test]
+    fn array_len() {
+        let cell = NearSafeCell::new([1, 2, 3]);
+        assert_eq!(cell.len(), 3);
+        assert!(!cell.is_empty());
+        assert!(NearSafeCell::new([0u8; 0]).is_empty());
+    }
+
+    #[test]
+    fn array_mut_unsafe() {
+        let cell = NearSafeCell::new([1, 2, 3, 4]);
+        let array = unsafe { cell.get_mut_unsafe_array() };
+        for elem in array.iter_mut() {
+            *elem *= 10;
+        }
+        assert_eq!(cell.get(), &[10, 20, 30, 40]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_len() {
+        use alloc::{vec, vec::Vec};
+        let mut cell = NearSafeCell::new(vec![1, 2, 3]);
+        assert_eq!(cell.len(), 3);
+        assert!(!cell.is_empty());
+        cell.get_mut().clear();
+        assert_eq!(cell.len(), 0);
+        assert!(cell.is_empty());
+        assert!(NearSafeCell::new(Vec::<u8>::new()).is_empty());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn swap_remove() {
+        let mut cell = NearSafeCell::new(alloc::vec![1, 2, 3, 4]);
+        assert_eq!(cell.swap_remove(1), 2);
+        assert_eq!(cell.get(), &[1, 4, 3]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "swap_remove index")]
+    fn swap_remove_out_of_bounds() {
+        let mut cell = NearSafeCell::new(alloc::vec![1, 2, 3]);
+        cell.swap_remove(3);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn two_vecs_mut() {
+        use alloc::vec::Vec;
+        struct Components {
+            positions: Vec<u32>,
+            velocities: Vec<i32>,
+        }
+        let cell = NearSafeCell::new(Components {
+            positions: alloc::vec![1, 2],
+            velocities: alloc::vec![-1, 1],
+        });
+        let (positions, velocities) = unsafe {
+            cell.two_vecs_mut(
+                |c| core::ptr::addr_of_mut!((*c).positions),
+                |c| core::ptr::addr_of_mut!((*c).velocities),
+            )
+        };
+        for (p, v) in positions.iter_mut().zip(velocities.iter_mut()) {
+            *p = p.wrapping_add_signed(*v);
+            *v *= 2;
+        }
+        assert_eq!(cell.get().positions, [0, 3]);
+        assert_eq!(cell.get().velocities, [-2, 2]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn map_collect() {
+        use alloc::{vec, vec::Vec};
+        let cell = NearSafeCell::new(vec![1u8, 2, 3]);
+        let mapped: Vec<u16> = cell.map_collect(|v| u16::from(v) * 300);
+        assert_eq!(mapped, [300, 600, 900]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn equivalent_lookup() {
+        use alloc::string::String;
+        let mut map = indexmap::IndexMap::new();
+        map.insert(NearSafeCell::new(String::from("answer")), 42);
+        assert_eq!(map.get("answer"), Some(&42));
+        assert_eq!(
+            map.get(&NearSafeCell::new(String::from("answer"))),
+            Some(&42)
+        );
+        assert_eq!(map.get("question"), None);
+    }
+
+    #[test]
+    fn hashbrown_keys() {
+        let mut map = hashbrown::HashMap::new();
+        map.insert(NearSafeCell::new(24u32), "24");
+        map.insert(NearSafeCell::new(42u32), "42");
+        assert_eq!(map.get(&NearSafeCell::new(42)), Some(&"42"));
+        assert_eq!(map.get(&24), Some(&"24"));
+        assert_eq!(map.get(&7), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn hashbrown_string_keys() {
+        use alloc::string::String;
+        let mut map = hashbrown::HashMap::new();
+        map.insert(NearSafeCell::new(String::from("answer")), 42);
+        assert_eq!(map.get("answer"), Some(&42));
+        assert_eq!(map.get(&String::from("answer")), Some(&42));
+        assert_eq!(
+            map.get(&NearSafeCell::new(String::from("answer"))),
+            Some(&42)
+        );
+    }
+
     include!("test_utilities.rs");
     #[test]
+    // Predates this lint and is kept as written.
+    #[allow(clippy::uninlined_format_args)]
     fn formatting() {
         let mut buffer = [0u8; 32];
         let cell = NearSafeCell::new(42);
@@ -252,4 +1569,993 @@ mod tests {
             "0x2a"
         );
     }
+
+    #[test]
+    fn debug_cells() {
+        let mut buffer = [0u8; 32];
+        let cells = [
+            NearSafeCell::new(1u8),
+            NearSafeCell::new(2),
+            NearSafeCell::new(3),
+        ];
+        assert_eq!(
+            format(
+                &mut buffer,
+                format_args!("{:?}", super::debug_cells(&cells))
+            )
+            .unwrap(),
+            "[1, 2, 3]"
+        );
+    }
+
+    #[test]
+    fn cell_ptr_formatting() {
+        struct CellPtr<'a>(&'a NearSafeCell<u32>);
+        impl Display for CellPtr<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                self.0.fmt_cell_ptr(f)
+            }
+        }
+        let mut buffer = [0u8; 32];
+        let mut expected = [0u8; 32];
+        let cell = NearSafeCell::new(42u32);
+        assert_eq!(
+            format(&mut buffer, format_args!("{}", CellPtr(&cell))).unwrap(),
+            format(&mut expected, format_args!("{:p}", cell.get_ptr())).unwrap()
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_unwrap_arc() {
+        use alloc::sync::Arc;
+        let arc = Arc::new(NearSafeCell::new(42));
+        assert_eq!(try_unwrap(arc), Ok(42));
+
+        let arc = Arc::new(NearSafeCell::new(42));
+        let other = Arc::clone(&arc);
+        let arc = try_unwrap(arc).unwrap_err();
+        assert!(Arc::ptr_eq(&arc, &other));
+        drop(other);
+        assert_eq!(try_unwrap(arc), Ok(42));
+    }
+
+    #[test]
+    fn pin_mut_unsafe() {
+        use core::{
+            future::Future,
+            task::{Context, Poll, Waker},
+        };
+        let cell = NearSafeCell::new(async { 42 });
+        let mut cx = Context::from_waker(Waker::noop());
+        let future = unsafe { cell.get_pin_mut_unsafe() };
+        assert_eq!(future.poll(&mut cx), Poll::Ready(42));
+    }
+
+    #[test]
+    fn inner() {
+        let mut cell = NearSafeCell::new([1, 2, 3]);
+        assert_eq!(cell.inner().len(), 3);
+        cell.inner_mut()[0] = 42;
+        assert_eq!(cell.inner(), &[42, 2, 3]);
+    }
+
+    #[test]
+    fn as_array_ref() {
+        let cell = NearSafeCell::new([1u8, 2, 3, 4, 5]);
+        assert_eq!(cell.as_array_ref::<_, 2>(), Some(&[1, 2]));
+        assert_eq!(cell.as_array_ref::<_, 5>(), Some(&[1, 2, 3, 4, 5]));
+        assert_eq!(cell.as_array_ref::<u8, 6>(), None);
+    }
+
+    #[test]
+    fn with_range_mut() {
+        let cell = NearSafeCell::new([0u8; 10]);
+        let len = unsafe {
+            cell.with_range_mut(3..6, |range| {
+                range.fill(1);
+                range.len()
+            })
+        };
+        assert_eq!(len, 3);
+        assert_eq!(cell.get(), &[0, 0, 0, 1, 1, 1, 0, 0, 0, 0]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn debug_catch() {
+        struct Panicking;
+        impl Debug for Panicking {
+            fn fmt(&self, _: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                panic!("Debug of Panicking")
+            }
+        }
+        assert_eq!(
+            std::format!("{:?}", NearSafeCell::new(Panicking).debug_catch()),
+            "NearSafeCell(<panicked>)"
+        );
+        assert_eq!(
+            std::format!("{:?}", NearSafeCell::new(42).debug_catch()),
+            "NearSafeCell(42)"
+        );
+    }
+
+    #[test]
+    fn reborrow() {
+        fn read(cell: &NearSafeCell<u32>) -> u32 {
+            *cell.get()
+        }
+        let mut cell = NearSafeCell::new(42);
+        assert_eq!(read(cell.reborrow()), 42);
+        *cell.get_mut() = 24;
+        assert_eq!(read(cell.as_shared()), 24);
+    }
+
+    #[test]
+    fn publish_consume() {
+        use core::sync::atomic::AtomicBool;
+        let cell = NearSafeCell::new(0u32);
+        let ready = AtomicBool::new(false);
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                unsafe { *cell.get_mut_unsafe() = 42 };
+                cell.publish();
+                ready.store(true, Ordering::Relaxed);
+            });
+            s.spawn(|| {
+                while !ready.load(Ordering::Relaxed) {
+                    core::hint::spin_loop();
+                }
+                cell.consume();
+                assert_eq!(cell.get(), &42);
+            });
+        });
+    }
+
+    #[test]
+    fn from_array() {
+        let cell: NearSafeCell<[u8; 4]> = [1, 2, 3, 4].into();
+        assert_eq!(cell.get()[0], 1);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn retain_unsafe() {
+        let cell = NearSafeCell::new(alloc::vec![1, 2, 3, 4, 5, 6]);
+        let shared = &cell;
+        unsafe { shared.retain_unsafe(|v| v % 2 == 0) };
+        assert_eq!(cell.get(), &[2, 4, 6]);
+    }
+
+    #[test]
+    fn project_ref() {
+        struct Pair {
+            left: u32,
+            right: [u8; 2],
+        }
+        let cell = NearSafeCell::new(Pair {
+            left: 42,
+            right: [1, 2],
+        });
+        assert_eq!(cell.project_ref(|p| &p.left), &42);
+        assert_eq!(cell.project_ref(|p| &p.right[..]), &[1, 2]);
+    }
+
+    #[test]
+    fn unwrap_drops_once() {
+        use core::cell::Cell;
+        struct DropCounter<'a>(&'a Cell<usize>);
+        impl Drop for DropCounter<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+        let drops = Cell::new(0);
+        let value = NearSafeCell::new(DropCounter(&drops)).unwrap();
+        assert_eq!(drops.get(), 0);
+        drop(value);
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn align_to_mut() {
+        let cell = NearSafeCell::new([0u8; 32]);
+        let (prefix, middle, suffix) = unsafe { cell.align_to_mut::<u32>() };
+        assert_eq!(prefix.len() + middle.len() * 4 + suffix.len(), 32);
+        assert!(middle.len() >= 7);
+        middle.fill(0x0101_0101);
+        let (prefix, suffix) = (prefix.len(), suffix.len());
+        assert!(cell.get()[..prefix].iter().all(|&b| b == 0));
+        assert!(cell.get()[prefix..32 - suffix].iter().all(|&b| b == 1));
+        assert!(cell.get()[32 - suffix..].iter().all(|&b| b == 0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn collect_set() {
+        let cells = [1, 2, 2, 3, 1].map(NearSafeCell::new);
+        let set = super::collect_set(cells);
+        assert_eq!(set.len(), 3);
+        assert!([1, 2, 3].iter().all(|v| set.contains(v)));
+    }
+
+    #[cfg(near_safe_cell_assert_single_thread)]
+    #[test]
+    fn assert_single_thread() {
+        let cell = NearSafeCell::new(0u32);
+        unsafe { *cell.get_mut_unsafe() = 1 };
+        unsafe { *cell.get_mut_unsafe() = 2 };
+        std::thread::scope(|s| {
+            let result = s.spawn(|| unsafe { *cell.get_mut_unsafe() = 3 }).join();
+            assert!(result.is_err());
+        });
+        assert_eq!(cell.get(), &2);
+
+        cell.publish();
+        std::thread::scope(|s| {
+            s.spawn(|| unsafe { *cell.get_mut_unsafe() = 3 });
+        });
+        assert_eq!(cell.get(), &3);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn map_mut() {
+        let mut cell = NearSafeCell::new(alloc::vec![1u8, 2]);
+        let len = cell.map_mut(|v| {
+            v.push(3);
+            v.len()
+        });
+        assert_eq!(len, 3);
+        assert_eq!(cell.get(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn eq_slice() {
+        let cell = NearSafeCell::new([1, 2, 3]);
+        assert_eq!(cell, [1, 2, 3]);
+        assert_ne!(cell, [1, 2, 4]);
+        assert_eq!(cell, [1, 2, 3][..]);
+        assert_ne!(cell, [1, 2][..]);
+    }
+
+    #[test]
+    fn chunks_mut_unsafe() {
+        let cell = NearSafeCell::new([0u8; 12]);
+        for (i, chunk) in (0u8..).zip(unsafe { cell.chunks_mut_unsafe(4) }) {
+            chunk.fill(i);
+        }
+        assert_eq!(cell.get(), &[0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2]);
+    }
+
+    #[cfg(not(near_safe_cell_assert_single_thread))]
+    #[test]
+    fn from_unsafe_cell_ref() {
+        let unsafe_cell = UnsafeCell::new(24);
+        let cell = NearSafeCell::from_unsafe_cell_ref(&unsafe_cell);
+        assert_eq!(cell.get(), &24);
+        unsafe { *cell.get_mut_unsafe() = 42 };
+        assert_eq!(unsafe_cell.into_inner(), 42);
+    }
+
+    #[test]
+    fn fetch_max_min() {
+        let cell = NearSafeCell::new(10u64);
+        assert_eq!(unsafe { cell.fetch_max(5) }, 10);
+        assert_eq!(cell.get(), &10);
+        assert_eq!(unsafe { cell.fetch_max(20) }, 10);
+        assert_eq!(cell.get(), &20);
+        assert_eq!(unsafe { cell.fetch_min(30) }, 20);
+        assert_eq!(cell.get(), &20);
+        assert_eq!(unsafe { cell.fetch_min(3) }, 20);
+        assert_eq!(cell.get(), &3);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn as_ref_str() {
+        fn len(s: impl AsRef<str>) -> usize {
+            s.as_ref().len()
+        }
+        let cell = NearSafeCell::new(alloc::string::String::from("answer"));
+        assert_eq!(len(&*cell), 6);
+        assert_eq!(len(cell), 6);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn extend_from_slice_unsafe() {
+        let cell = NearSafeCell::new(alloc::vec::Vec::new());
+        let shared = &cell;
+        unsafe {
+            shared.extend_from_slice_unsafe(&[1, 2]);
+            shared.extend_from_slice_unsafe(&[3]);
+        }
+        assert_eq!(cell.get(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn shared_ref() {
+        let cell = NearSafeCell::new(0u32);
+        let handle = cell.shared_ref();
+        let increment = || unsafe { *handle.get_mut_unsafe() += 1 };
+        let read = || *handle.get();
+        increment();
+        increment();
+        assert_eq!(read(), 2);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn truncate_unsafe() {
+        let vec = NearSafeCell::new(alloc::vec![1u8, 2, 3, 4]);
+        unsafe { vec.truncate_unsafe(2) };
+        assert_eq!(vec.get(), &[1, 2]);
+
+        let string = NearSafeCell::new(alloc::string::String::from("answer"));
+        unsafe { string.truncate_unsafe(3) };
+        assert_eq!(string.get(), "ans");
+    }
+
+    #[test]
+    fn fingerprint() {
+        let cell = NearSafeCell::new([1u8, 2, 3]);
+        let before = cell.fingerprint();
+        assert_eq!(cell.fingerprint(), before);
+        assert_eq!(NearSafeCell::new([1u8, 2, 3]).fingerprint(), before);
+        unsafe { cell.get_mut_unsafe()[1] = 42 };
+        assert_ne!(cell.fingerprint(), before);
+    }
+
+    #[test]
+    fn cell_static() {
+        define_cell_static!(COUNTER: u32 = 1);
+        assert_eq!(COUNTER().get(), &1);
+        unsafe { *COUNTER().get_mut_unsafe() += 1 };
+        assert_eq!(COUNTER().get(), &2);
+        assert!(core::ptr::eq(COUNTER(), COUNTER()));
+    }
+
+    #[test]
+    fn shifts() {
+        assert_eq!(NearSafeCell::new(0b101u8) << 2, 0b10100u8);
+        assert_eq!(NearSafeCell::new(0b101u8) >> 2, 0b1u8);
+        let mut cell = NearSafeCell::new(0b101u8);
+        cell <<= 1;
+        assert_eq!(cell.get(), &0b1010);
+        cell >>= 3u32;
+        assert_eq!(cell.get(), &0b1);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn insert_unsafe() {
+        let cell = NearSafeCell::new(alloc::vec![1, 2, 4]);
+        unsafe { cell.insert_unsafe(2, 3) };
+        assert_eq!(cell.get(), &[1, 2, 3, 4]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "insertion index")]
+    fn insert_unsafe_out_of_bounds() {
+        let cell = NearSafeCell::new(alloc::vec![1, 2, 3]);
+        unsafe { cell.insert_unsafe(4, 5) };
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn drain_iter() {
+        use alloc::vec::Vec;
+        let mut cell = NearSafeCell::new(alloc::vec![1, 2, 3, 4, 5]);
+        assert_eq!(cell.drain_iter(1..4).collect::<Vec<_>>(), [2, 3, 4]);
+        assert_eq!(cell.get(), &[1, 5]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "range end index")]
+    fn drain_iter_out_of_bounds() {
+        let mut cell = NearSafeCell::new(alloc::vec![1, 2, 3]);
+        cell.drain_iter(1..4);
+    }
+
+    #[test]
+    fn unsafe_cell_conversions() {
+        static CELL: NearSafeCell<u32> = NearSafeCell::from_unsafe_cell(UnsafeCell::new(24));
+        assert_eq!(CELL.get(), &24);
+        let cell: NearSafeCell<u32> = UnsafeCell::new(42).into();
+        assert_eq!(cell.get(), &42);
+        let unsafe_cell: UnsafeCell<u32> = cell.into();
+        assert_eq!(unsafe_cell.into_inner(), 42);
+    }
+
+    #[test]
+    fn sum() {
+        let cell = NearSafeCell::new(1..5u32);
+        assert_eq!(cell.sum::<u32>(), 10);
+    }
+
+    #[cfg(all(feature = "std", not(near_safe_cell_assert_single_thread)))]
+    #[test]
+    fn ffi_round_trip() {
+        extern "C" fn modify(ptr: *mut u32) {
+            unsafe { *ptr += 1 };
+        }
+        let cell = NearSafeCell::new(41u32);
+        modify(cell.get_mut_ptr());
+        assert_eq!(cell.get(), &42);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn unsized_inner() {
+        use alloc::boxed::Box;
+        let slice: Box<NearSafeCell<[i32]>> = Box::new(NearSafeCell::new([1, 2, 3]));
+        assert_eq!(slice.get(), &[1, 2, 3]);
+        unsafe { slice.get_mut_unsafe()[1] = 42 };
+        assert_eq!(slice.get(), &[1, 42, 3]);
+        assert_eq!(slice.get_ptr().len(), 3);
+        assert_eq!(slice.as_non_null_slice().len(), 3);
+
+        let mut calls = 0;
+        let f: Box<NearSafeCell<dyn FnMut() + '_>> = Box::new(NearSafeCell::new(|| calls += 1));
+        unsafe { f.get_mut_unsafe()() };
+        drop(f);
+        assert_eq!(calls, 1);
+    }
+
+    #[cfg(not(near_safe_cell_assert_single_thread))]
+    #[test]
+    fn from_mut() {
+        let mut value = [1, 2, 3];
+        {
+            let cell = NearSafeCell::from_mut(&mut value);
+            let shared = cell.shared_ref();
+            unsafe { shared.get_mut_unsafe()[0] = 42 };
+            assert_eq!(cell.get(), &[42, 2, 3]);
+        }
+        assert_eq!(value, [42, 2, 3]);
+    }
+
+    #[cfg(not(near_safe_cell_assert_single_thread))]
+    #[test]
+    fn raw_get() {
+        let slot = core::mem::MaybeUninit::<NearSafeCell<u32>>::uninit();
+        unsafe { NearSafeCell::raw_get(slot.as_ptr()).write(42) };
+        let cell = unsafe { slot.assume_init() };
+        assert_eq!(cell.get(), &42);
+    }
+
+    #[test]
+    fn replace_array_unsafe() {
+        let cell = NearSafeCell::new([1, 2, 3, 4]);
+        let shared = &cell;
+        assert_eq!(
+            unsafe { shared.replace_array_unsafe([5, 6, 7, 8]) },
+            [1, 2, 3, 4]
+        );
+        assert_eq!(cell.get(), &[5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn slice_keys() {
+        let mut map = hashbrown::HashMap::new();
+        map.insert(NearSafeCell::new([1u8, 2, 3]), "123");
+        map.insert(NearSafeCell::new([4u8, 5, 6]), "456");
+        let key: &[u8] = &[4, 5, 6];
+        assert_eq!(map.get(key), Some(&"456"));
+        assert_eq!(map.get(&[1u8, 2, 3][..]), Some(&"123"));
+        assert_eq!(map.get(&[1u8, 2][..]), None);
+    }
+
+    #[test]
+    fn get_disjoint_mut_unchecked() {
+        let cell = NearSafeCell::new([1, 2, 3, 4, 5]);
+        let [a, b, c] = unsafe { cell.get_disjoint_mut_unchecked([0, 2, 4]) };
+        core::mem::swap(a, c);
+        *b *= 10;
+        *c += *a;
+        assert_eq!(cell.get(), &[5, 2, 30, 4, 6]);
+        let [low, high] = unsafe { cell.get_disjoint_mut_unchecked([0..2, 2..5]) };
+        low.copy_from_slice(&high[..2]);
+        assert_eq!(cell.get(), &[30, 4, 30, 4, 6]);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "get_disjoint_mut_unchecked called with overlapping indices")]
+    fn get_disjoint_mut_unchecked_overlapping() {
+        let cell = NearSafeCell::new([1, 2, 3, 4, 5]);
+        unsafe { cell.get_disjoint_mut_unchecked([0..2, 1..3]) };
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Config {
+            name: std::string::String,
+            retries: NearSafeCell<u32>,
+            limits: NearSafeCell<[u8; 2]>,
+        }
+        assert_eq!(
+            serde_json::to_string(&NearSafeCell::new(42u32)).unwrap(),
+            "42"
+        );
+        assert_eq!(
+            serde_json::from_str::<NearSafeCell<u32>>("42")
+                .unwrap()
+                .unwrap(),
+            42
+        );
+        let config = Config {
+            name: "answer".into(),
+            retries: NearSafeCell::new(3),
+            limits: NearSafeCell::new([1, 2]),
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(json, r#"{"name":"answer","retries":3,"limits":[1,2]}"#);
+        assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+    }
+
+    #[test]
+    fn fmt_write() {
+        use core::fmt::Write;
+        #[derive(Default)]
+        struct Counter {
+            chars: usize,
+            strs: usize,
+        }
+        impl Write for Counter {
+            fn write_str(&mut self, _: &str) -> core::fmt::Result {
+                self.strs += 1;
+                Ok(())
+            }
+            fn write_char(&mut self, _: char) -> core::fmt::Result {
+                self.chars += 1;
+                Ok(())
+            }
+        }
+        let (c, s) = ('x', "xy");
+        let mut cell = NearSafeCell::new(Counter::default());
+        write!(cell, "{c}").unwrap();
+        cell.write_char('y').unwrap();
+        assert_eq!((cell.get().chars, cell.get().strs), (2, 0));
+        write!(cell, "{s}").unwrap();
+        assert_eq!((cell.get().chars, cell.get().strs), (2, 1));
+    }
+
+    #[test]
+    fn replace_take_swap() {
+        let mut a = NearSafeCell::new(1);
+        let mut b = NearSafeCell::new(2);
+        a.swap_with(&mut b);
+        assert_eq!((a.get(), b.get()), (&2, &1));
+        assert_eq!(a.replace_value(3), 2);
+        assert_eq!(a.take_value(), 3);
+        assert_eq!(a.get(), &0);
+        let shared = &b;
+        assert_eq!(unsafe { shared.replace_value_unchecked(4) }, 1);
+        assert_eq!(b.unwrap(), 4);
+    }
+
+    #[test]
+    fn replace_take_swap_of_target() {
+        let mut array = NearSafeCell::new([1, 2, 3]);
+        array.swap(0, 2);
+        assert_eq!(array.get(), &[3, 2, 1]);
+        let mut option = NearSafeCell::new(Some(1));
+        assert_eq!(option.replace(2), Some(1));
+        assert_eq!(option.take(), Some(2));
+        assert_eq!(option.get(), &None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn dedup_unsafe() {
+        let cell = NearSafeCell::new(alloc::vec![1, 1, 2, 3, 3, 3, 1]);
+        let shared = &cell;
+        unsafe { shared.dedup_unsafe() };
+        assert_eq!(cell.get(), &[1, 2, 3, 1]);
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn defmt_format() {
+        // Actually logging needs a global defmt logger, so this only checks that the impl resolves.
+        fn assert_format<T: defmt::Format + ?Sized>() {}
+        assert_format::<NearSafeCell<u32>>();
+        assert_format::<NearSafeCell<[u8]>>();
+    }
+
+    #[test]
+    fn iter_mut_enumerate_unsafe() {
+        let cell = NearSafeCell::new([0usize; 4]);
+        let shared = &cell;
+        for (i, elem) in unsafe { shared.iter_mut_enumerate_unsafe() } {
+            *elem = i;
+        }
+        assert_eq!(cell.get(), &[0, 1, 2, 3]);
+    }
+
+    #[cfg(all(feature = "alloc", not(near_safe_cell_assert_single_thread)))]
+    #[test]
+    fn as_cells() {
+        use alloc::boxed::Box;
+        let array = NearSafeCell::new([1, 2, 3]);
+        let [a, _, c] = array.as_array_of_cells();
+        unsafe { core::mem::swap(a.get_mut_unsafe(), c.get_mut_unsafe()) };
+        assert_eq!(array.get(), &[3, 2, 1]);
+        let slice: Box<NearSafeCell<[i32]>> = Box::new(array);
+        let cells = slice.as_slice_of_cells();
+        unsafe { *cells[0].get_mut_unsafe() += *cells[1].get() };
+        unsafe { *cells[2].get_mut_unsafe() *= 10 };
+        assert_eq!(slice.get(), &[5, 2, 10]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn box_into_cell() {
+        use alloc::boxed::Box;
+        let boxed = Box::new(42);
+        let ptr = core::ptr::from_ref(&*boxed);
+        let cell: NearSafeCell<Box<i32>> = boxed.into();
+        assert!(core::ptr::eq(core::ptr::from_ref(&**cell.get()), ptr));
+        assert_eq!(cell.unwrap(), Box::new(42));
+    }
+
+    #[cfg(all(feature = "alloc", not(near_safe_cell_assert_single_thread)))]
+    #[test]
+    fn box_reinterpretation() {
+        use alloc::{boxed::Box, vec};
+        let boxed: Box<[i32]> = vec![1, 2, 3].into_boxed_slice();
+        let ptr = boxed.as_ptr();
+        let cell = NearSafeCell::from_box(boxed);
+        unsafe { cell.get_mut_unsafe()[0] = 42 };
+        assert_eq!(cell.get().as_ptr(), ptr);
+        let boxed = NearSafeCell::into_box(cell);
+        assert_eq!(boxed.as_ptr(), ptr);
+        assert_eq!(*boxed, [42, 2, 3]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn split_at_mut_unchecked() {
+        use alloc::boxed::Box;
+        let cell: Box<NearSafeCell<[u8]>> = Box::new(NearSafeCell::new([1, 2, 3, 4, 5]));
+        let (low, high) = unsafe { cell.split_at_mut_unchecked(2) };
+        low.swap_with_slice(&mut high[1..]);
+        high[0] = 0;
+        assert_eq!(cell.get(), &[4, 5, 0, 1, 2]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "mid > len")]
+    fn split_at_mut_unchecked_out_of_bounds() {
+        use alloc::boxed::Box;
+        let cell: Box<NearSafeCell<[u8]>> = Box::new(NearSafeCell::new([1, 2, 3]));
+        unsafe { cell.split_at_mut_unchecked(4) };
+    }
+
+    #[test]
+    fn swap_unsafe_at() {
+        let cell = NearSafeCell::new([1u8, 2, 3, 4]);
+        let shared = &cell;
+        unsafe { shared.swap_unsafe_at(0, 3) };
+        assert_eq!(cell.get(), &[4, 2, 3, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn swap_unsafe_at_out_of_bounds() {
+        let cell = NearSafeCell::new([1u8, 2, 3, 4]);
+        unsafe { cell.swap_unsafe_at(0, 4) };
+    }
+
+    #[cfg(all(feature = "bytemuck", not(near_safe_cell_assert_single_thread)))]
+    #[test]
+    fn zeroable() {
+        let cell: NearSafeCell<u32> = bytemuck::Zeroable::zeroed();
+        assert_eq!(cell.get(), &0);
+        let cells: NearSafeCell<[NearSafeCell<u8>; 3]> = bytemuck::Zeroable::zeroed();
+        assert_eq!(cells.unwrap().map(NearSafeCell::unwrap), [0; 3]);
+    }
+
+    #[test]
+    fn const_eq() {
+        const _: () = assert!(NearSafeCell::new(3u32).const_eq(&3));
+        const _: () = assert!(!NearSafeCell::new('a').const_eq(&'b'));
+        const _: () = assert!(NearSafeCell::new(true).const_eq(&true));
+        assert!(NearSafeCell::new(-1i64).const_eq(&-1));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[allow(clippy::mutable_key_type)]
+    fn btree_keys() {
+        use alloc::{collections::BTreeMap, vec::Vec};
+        let mut map = BTreeMap::new();
+        for key in [3, 1, 2] {
+            map.insert(NearSafeCell::new(key), key * 10);
+        }
+        assert_eq!(map.get(&NearSafeCell::new(2)), Some(&20));
+        assert_eq!(map.get(&2), Some(&20));
+        let keys: Vec<_> = map.keys().map(NearSafeCell::get).copied().collect();
+        assert_eq!(keys, [1, 2, 3]);
+        assert!(NearSafeCell::new(1).cmp(&NearSafeCell::new(2)).is_lt());
+    }
+
+    #[test]
+    fn as_near_safe_cell() {
+        fn read<T: Copy>(cell: impl AsNearSafeCell<T>) -> T {
+            *cell.as_near_safe_cell().get()
+        }
+        let mut cell = NearSafeCell::new(42);
+        assert_eq!(read(&cell), 42);
+        assert_eq!(read(&mut cell), 42);
+        assert_eq!(cell.as_ref(), &42);
+        #[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+        assert_eq!(read(Arc::new(NearSafeCell::new(1))), 1);
+        assert_eq!(read(cell), 42);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn clone_and_from() {
+        use std::string::String;
+        let cell: NearSafeCell<u32> = 5.into();
+        assert_eq!(cell.get(), &5);
+        let original = NearSafeCell::new(String::from("answer"));
+        let mut clone = original.clone();
+        clone.get_mut().push_str(": 42");
+        assert_eq!(original.get(), "answer");
+        assert_eq!(clone.get(), "answer: 42");
+    }
+
+    #[test]
+    fn zip_apply() {
+        let dst = NearSafeCell::new([1, 2, 3]);
+        let src = NearSafeCell::new([10, 20, 30]);
+        unsafe { super::zip_apply(&dst, &src, |d, s| *d += 2 * s) };
+        assert_eq!(dst.get(), &[21, 42, 63]);
+        assert_eq!(src.get(), &[10, 20, 30]);
+    }
+
+    #[test]
+    #[should_panic(expected = "zip_apply called with slices of different lengths")]
+    fn zip_apply_length_mismatch() {
+        let dst = NearSafeCell::new([1, 2, 3]);
+        let src = NearSafeCell::new([1, 2]);
+        unsafe { super::zip_apply(&dst, &src, |d, s| *d += s) };
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn map() {
+        use alloc::string::{String, ToString};
+        let cell = NearSafeCell::new(41u8).map(|v| v + 1);
+        assert_eq!(cell.get(), &42);
+        let cell: NearSafeCell<String> = cell.map(|v| v.to_string());
+        assert_eq!(cell.get(), "42");
+        assert_eq!(cell.map_ref(String::len), 2);
+        assert_eq!(cell.unwrap(), "42");
+    }
+
+    #[test]
+    fn assert_cell_eq() {
+        let cell = NearSafeCell::new([1, 2, 3]);
+        assert_cell_eq!(cell, [1, 2, 3]);
+        assert_cell_eq!(&cell, [1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion `cell == expected` failed\n    cell: 1\nexpected: 2")]
+    fn assert_cell_eq_failing() {
+        assert_cell_eq!(NearSafeCell::new(1), 2);
+    }
+
+    #[cfg(not(near_safe_cell_assert_single_thread))]
+    #[test]
+    fn unsafe_cell_refs() {
+        let unsafe_cell = UnsafeCell::new(1);
+        let cell = NearSafeCell::from_unsafe_cell_ref(&unsafe_cell);
+        unsafe { *unsafe_cell.get() += 1 };
+        assert_eq!(cell.get(), &2);
+        unsafe { *cell.get_mut_unsafe() += 1 };
+        assert!(core::ptr::eq(
+            cell.as_unsafe_cell(),
+            core::ptr::from_ref(&unsafe_cell)
+        ));
+        assert_eq!(unsafe { *cell.as_unsafe_cell().get() }, 3);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn insert_sorted_unsafe() {
+        let cell = NearSafeCell::new(alloc::vec![1, 3, 5]);
+        let shared = &cell;
+        assert_eq!(unsafe { shared.insert_sorted_unsafe(4) }, 2);
+        assert_eq!(unsafe { shared.insert_sorted_unsafe(0) }, 0);
+        assert_eq!(unsafe { shared.insert_sorted_unsafe(3) }, 3);
+        assert_eq!(unsafe { shared.insert_sorted_unsafe(9) }, 6);
+        assert_eq!(cell.get(), &[0, 1, 3, 3, 4, 5, 9]);
+    }
+
+    #[test]
+    fn get_pinned_mut_unsafe() {
+        struct Pinned {
+            polls: u32,
+            _pin: core::marker::PhantomPinned,
+        }
+        let cell = core::pin::pin!(NearSafeCell::new(Pinned {
+            polls: 0,
+            _pin: core::marker::PhantomPinned,
+        }));
+        let cell = cell.into_ref();
+        for _ in 0..2 {
+            let pinned = unsafe { cell.get_pinned_mut_unsafe() };
+            unsafe { pinned.get_unchecked_mut().polls += 1 };
+        }
+        assert_eq!(cell.get().polls, 2);
+    }
+
+    #[test]
+    fn slice_mut_unsafe() {
+        let cell = NearSafeCell::new([0u8; 8]);
+        unsafe { cell.slice_mut_unsafe(..).fill(1) };
+        unsafe { cell.slice_mut_unsafe(6..).fill(2) };
+        unsafe { cell.slice_mut_unsafe(..2).fill(3) };
+        unsafe { cell.slice_mut_unsafe(..=2)[2] = 4 };
+        unsafe { cell.slice_mut_unsafe(3..5).fill(5) };
+        unsafe { cell.slice_mut_unsafe(5..=5)[0] = 6 };
+        assert_eq!(cell.get(), &[3, 3, 4, 5, 5, 6, 2, 2]);
+        assert!(unsafe { cell.slice_mut_unsafe(8..) }.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 9 out of range")]
+    fn slice_mut_unsafe_out_of_bounds() {
+        let cell = NearSafeCell::new([0u8; 8]);
+        unsafe { cell.slice_mut_unsafe(2..9) };
+    }
+
+    #[test]
+    fn maybe_uninit() {
+        let mut cell = NearSafeCell::new(MaybeUninit::<[u32; 2]>::uninit());
+        cell.write([1, 2])[1] = 3;
+        let shared = &cell;
+        let value = unsafe { shared.assume_init_get_mut_unsafe() };
+        value[0] += 1;
+        assert_eq!(unsafe { cell.unwrap().assume_init() }, [2, 3]);
+    }
+
+    #[test]
+    fn eq_inner() {
+        assert!(NearSafeCell::new(42) == 42);
+        assert!(NearSafeCell::new(42) != 7);
+        assert_eq!(NearSafeCell::new([1, 2, 3]), [1, 2, 3]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn eq_inner_string() {
+        use alloc::string::String;
+        let cell = NearSafeCell::new(String::from("cell"));
+        assert_eq!(cell, String::from("cell"));
+        assert_ne!(cell, String::from("other"));
+    }
+
+    #[test]
+    fn into_iter() {
+        let mut sum = 0;
+        for elem in NearSafeCell::new([1, 2, 3]) {
+            sum += elem;
+        }
+        assert_eq!(sum, 6);
+    }
+
+    #[test]
+    fn index_disjoint_mut_unchecked() {
+        let cell = NearSafeCell::new([1u8, 2, 3, 4]);
+        let (low, high) = unsafe { cell.index_disjoint_mut_unchecked(0..2, 2..4) };
+        low.swap_with_slice(high);
+        high[0] *= 10;
+        assert_eq!(cell.get(), &[3, 4, 10, 2]);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "index_disjoint_mut_unchecked called with overlapping indices")]
+    fn index_disjoint_mut_unchecked_overlapping() {
+        let cell = NearSafeCell::new([1u8, 2, 3, 4]);
+        unsafe { cell.index_disjoint_mut_unchecked(0..3, 2..4) };
+    }
+
+    #[test]
+    fn with_mut_unsafe() {
+        let total = NearSafeCell::new(1);
+        let step = NearSafeCell::new(10);
+        let doubled = unsafe {
+            total.with_mut_unsafe(|total| {
+                *total += *step.get();
+                *total * 2
+            })
+        };
+        assert_eq!(doubled, 22);
+        unsafe { step.with_mut_unsafe(|step| *step = *total.get()) };
+        assert_eq!((total.get(), step.get()), (&11, &11));
+    }
+
+    #[cfg(all(feature = "unsize", feature = "alloc"))]
+    #[test]
+    fn coerce_unsized() {
+        use alloc::boxed::Box;
+        let sized: NearSafeCell<Box<[i32; 3]>> = NearSafeCell::new(Box::new([1, 2, 3]));
+        let unsized_: NearSafeCell<Box<[i32]>> = sized;
+        assert_eq!(unsized_.get()[..], [1, 2, 3]);
+        let array = [4, 5, 6];
+        let sized: NearSafeCell<&[i32; 3]> = NearSafeCell::new(&array);
+        let unsized_: NearSafeCell<&[i32]> = sized;
+        assert_eq!(unsized_.get().len(), 3);
+        // Coercing behind a pointer to the cell needs no feature.
+        let boxed: Box<NearSafeCell<[i32]>> = Box::new(NearSafeCell::new([1, 2, 3]));
+        assert_eq!(boxed.get(), &[1, 2, 3][..]);
+    }
+
+    #[test]
+    fn try_new() {
+        let cell = NearSafeCell::try_new(|| "42".parse::<u8>()).unwrap();
+        assert_eq!(cell.get(), &42);
+        assert!(NearSafeCell::try_new(|| "x".parse::<u8>()).is_err());
+    }
+
+    #[test]
+    fn try_map() {
+        let cell = NearSafeCell::new("42").try_map(str::parse::<u8>).unwrap();
+        assert_eq!(cell.get(), &42);
+        let cell = cell.try_map(|v| char::from_digit(u32::from(v) % 10, 10).ok_or(v));
+        assert_eq!(cell.unwrap().get(), &'2');
+        let cell = NearSafeCell::new(300u16).try_map(<u8 as core::convert::TryFrom<u16>>::try_from);
+        assert!(cell.is_err());
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "get_disjoint_mut_unchecked called with an out of bounds index")]
+    fn get_disjoint_mut_unchecked_out_of_bounds() {
+        let cell = NearSafeCell::new([1, 2, 3]);
+        unsafe { cell.get_disjoint_mut_unchecked([0, 3]) };
+    }
+}
+
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+
+    #[test]
+    fn loom_ordered_get_mut_unsafe() {
+        loom::model(|| {
+            let cell = loom::sync::Arc::new(NearSafeCell::new(0));
+            let other = loom::sync::Arc::clone(&cell);
+            loom::thread::spawn(move || unsafe { *other.get_mut_unsafe() += 1 })
+                .join()
+                .unwrap();
+            unsafe { *cell.get_mut_unsafe() += 1 };
+            assert_eq!(cell.get(), &2);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Causality violation")]
+    fn loom_racing_get_mut_unsafe() {
+        loom::model(|| {
+            let cell = loom::sync::Arc::new(NearSafeCell::new(0));
+            let other = loom::sync::Arc::clone(&cell);
+            let handle = loom::thread::spawn(move || unsafe { *other.get_mut_unsafe() += 1 });
+            unsafe { *cell.get_mut_unsafe() += 1 };
+            handle.join().unwrap();
+        });
+    }
 }
@@ -0,0 +1,133 @@
+use crate::{debug_borrows::ExclusiveBorrow, NearSafeCell};
+use alloc::sync::Arc;
+use core::{
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+};
+
+impl<T: ?Sized> NearSafeCell<T> {
+    /// Returns an [`OwnedBorrowGuard`] granting `&mut T` access to the wrapped `T` for as long as it lives, keeping the cell alive through `self`.
+    /// Unlike a `&mut T`, the guard is `'static` if `T` is, so it can e.g. be held across `.await` points of a spawned future.
+    /// With the `debug-borrows` feature the borrow stays registered until the guard is dropped.
+    /// # Safety
+    /// There exists no other `&T` or `&mut T` to the wrapped `T` currently and until the returned [`OwnedBorrowGuard`] is dropped,
+    /// including through other clones of the [`Arc`] and across every `.await` point the guard is held over.
+    pub unsafe fn borrow_mut_owned(self: Arc<Self>) -> OwnedBorrowGuard<T> {
+        let borrow = self.borrow_exclusive();
+        self.assert_single_thread();
+        OwnedBorrowGuard {
+            _borrow: borrow,
+            cell: self,
+        }
+    }
+}
+
+/// Exclusive access to the value wrapped in an [`Arc<NearSafeCell<T>>`], returned by [`NearSafeCell::borrow_mut_owned`].
+pub struct OwnedBorrowGuard<T: ?Sized> {
+    /// Declared first so the borrow is released before the [`Arc`] may drop the cell.
+    _borrow: ExclusiveBorrow,
+    cell: Arc<NearSafeCell<T>>,
+}
+
+impl<T: ?Sized> OwnedBorrowGuard<T> {
+    /// Consumes this [`OwnedBorrowGuard`], ending the exclusive access and returning the [`Arc`] it kept alive.
+    #[must_use]
+    pub fn into_arc(guard: Self) -> Arc<NearSafeCell<T>> {
+        guard.cell
+    }
+}
+
+impl<T: ?Sized> Deref for OwnedBorrowGuard<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        // Safety: The contract of `NearSafeCell::borrow_mut_owned` makes this guard the only access to the wrapped `T`.
+        unsafe { &*self.cell.get_ptr() }
+    }
+}
+impl<T: ?Sized> DerefMut for OwnedBorrowGuard<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // Safety: The contract of `NearSafeCell::borrow_mut_owned` makes this guard the only access to the wrapped `T`.
+        unsafe { &mut *self.cell.get_mut_ptr() }
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for OwnedBorrowGuard<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("OwnedBorrowGuard").field(&&**self).finish()
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll, Waker},
+    };
+
+    /// Returns [`Poll::Pending`] once before completing, like an async runtime's `yield_now`.
+    struct YieldNow(bool);
+    impl Future for YieldNow {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                Poll::Pending
+            }
+        }
+    }
+
+    fn assert_send_static<F: Future + Send + 'static>(future: F) -> F {
+        future
+    }
+
+    #[test]
+    fn borrow_mut_owned() {
+        let cell = Arc::new(NearSafeCell::new(1u32));
+        let mut guard = unsafe { Arc::clone(&cell).borrow_mut_owned() };
+        let future = assert_send_static(async move {
+            *guard += 1;
+            YieldNow(false).await;
+            *guard *= 10;
+            OwnedBorrowGuard::into_arc(guard)
+        });
+        let mut future = core::pin::pin!(future);
+        let mut context = Context::from_waker(Waker::noop());
+        assert!(future.as_mut().poll(&mut context).is_pending());
+        let Poll::Ready(returned) = future.as_mut().poll(&mut context) else {
+            panic!("future did not complete after yielding once");
+        };
+        assert!(Arc::ptr_eq(&returned, &cell));
+        assert_eq!(cell.get(), &20);
+    }
+
+    #[cfg(feature = "debug-borrows")]
+    #[test]
+    #[should_panic(expected = "NearSafeCell accessed while it is mutably borrowed")]
+    fn borrow_mut_owned_then_get() {
+        let cell = Arc::new(NearSafeCell::new(1));
+        let _guard = unsafe { Arc::clone(&cell).borrow_mut_owned() };
+        cell.get();
+    }
+
+    #[cfg(feature = "debug-borrows")]
+    #[test]
+    #[should_panic(expected = "NearSafeCell mutably borrowed while it is already mutably borrowed")]
+    fn borrow_mut_owned_twice() {
+        let cell = Arc::new(NearSafeCell::new(1));
+        let _guard = unsafe { Arc::clone(&cell).borrow_mut_owned() };
+        let _other = unsafe { cell.borrow_mut_owned() };
+    }
+
+    #[cfg(feature = "debug-borrows")]
+    #[test]
+    fn borrow_mut_owned_released() {
+        let cell = Arc::new(NearSafeCell::new(1));
+        let guard = unsafe { Arc::clone(&cell).borrow_mut_owned() };
+        drop(OwnedBorrowGuard::into_arc(guard));
+        assert_eq!(cell.get(), &1);
+    }
+}
@@ -4,6 +4,8 @@ struct Buffer<'a> {
     used: usize,
 }
 #[cfg(test)]
+// The test utilities predate the lints allowed on them and are kept as written.
+#[allow(clippy::wrong_self_convention)]
 impl<'a> Buffer<'a> {
     fn new(buffer: &'a mut [u8]) -> Self {
         Buffer { buffer, used: 0 }
@@ -17,6 +19,7 @@ impl<'a> Buffer<'a> {
     }
 }
 #[cfg(test)]
+#[allow(clippy::elidable_lifetime_names)]
 impl<'a> core::fmt::Write for Buffer<'a> {
     fn write_str(&mut self, s: &str) -> core::fmt::Result {
         if self.used > self.buffer.len() {
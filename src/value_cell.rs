@@ -0,0 +1,78 @@
+use core::cell::UnsafeCell;
+
+/// The cell backing every [`NearSafeCell`](crate::NearSafeCell), a plain [`UnsafeCell`] unless the `loom` cfg is enabled.
+#[cfg(not(loom))]
+#[repr(transparent)]
+pub(crate) struct ValueCell<T: ?Sized>(UnsafeCell<T>);
+
+#[cfg(not(loom))]
+impl<T> ValueCell<T> {
+    pub(crate) const fn new(value: T) -> Self {
+        Self(UnsafeCell::new(value))
+    }
+    pub(crate) const fn from_unsafe_cell(cell: UnsafeCell<T>) -> Self {
+        Self(cell)
+    }
+    pub(crate) fn into_unsafe_cell(self) -> UnsafeCell<T> {
+        self.0
+    }
+    pub(crate) fn into_inner(self) -> T {
+        self.0.into_inner()
+    }
+}
+
+#[cfg(all(feature = "unsize", not(loom)))]
+impl<T: core::ops::CoerceUnsized<U>, U> core::ops::CoerceUnsized<ValueCell<U>> for ValueCell<T> {}
+
+#[cfg(not(loom))]
+impl<T: ?Sized> ValueCell<T> {
+    pub(crate) const fn get(&self) -> *mut T {
+        self.0.get()
+    }
+    pub(crate) const fn get_const(&self) -> *const T {
+        self.0.get()
+    }
+    pub(crate) fn get_mut(&mut self) -> &mut T {
+        self.0.get_mut()
+    }
+    pub(crate) const fn as_unsafe_cell(&self) -> &UnsafeCell<T> {
+        &self.0
+    }
+}
+
+/// The cell backing every [`NearSafeCell`](crate::NearSafeCell), loom's instrumented one since the `loom` cfg is enabled.
+///
+/// Every pointer handed out counts as an access at the time it is taken, immutable for [`Self::get_const`] and mutable otherwise,
+/// letting `loom::model` catch accesses from different threads that are not ordered by a happens-before relation.
+#[cfg(loom)]
+pub(crate) struct ValueCell<T: ?Sized>(loom::cell::UnsafeCell<T>);
+
+#[cfg(loom)]
+impl<T> ValueCell<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self(loom::cell::UnsafeCell::new(value))
+    }
+    pub(crate) fn from_unsafe_cell(cell: UnsafeCell<T>) -> Self {
+        Self::new(cell.into_inner())
+    }
+    pub(crate) fn into_unsafe_cell(self) -> UnsafeCell<T> {
+        UnsafeCell::new(self.into_inner())
+    }
+    pub(crate) fn into_inner(self) -> T {
+        self.0.into_inner()
+    }
+}
+
+#[cfg(loom)]
+impl<T: ?Sized> ValueCell<T> {
+    pub(crate) fn get(&self) -> *mut T {
+        self.0.with_mut(|ptr| ptr)
+    }
+    pub(crate) fn get_const(&self) -> *const T {
+        self.0.with(|ptr| ptr)
+    }
+    pub(crate) fn get_mut(&mut self) -> &mut T {
+        // Safety: We have exclusive access to self, loom merely gets to record it.
+        unsafe { &mut *self.get() }
+    }
+}